@@ -3,8 +3,9 @@ use std::{
     collections::{HashMap, HashSet},
 };
 
+use advent_of_code::dot::Graph;
 use itertools::Itertools;
-advent_of_code::solution!(5);
+advent_of_code::solution!(5, dot = to_dot);
 
 pub fn part_one(input: &str) -> Option<u32> {
     let mut map: HashMap<&str, HashSet<&str>> = HashMap::new();
@@ -90,6 +91,20 @@ pub fn part_two(input: &str) -> Option<u32> {
     Some(result)
 }
 
+/// The page ordering rules, an edge `a -> b` meaning that `a` is printed before `b`.
+fn to_dot(input: &str) -> Option<Graph> {
+    let (orders, _) = input.split_once("\n\n")?;
+    let mut graph = Graph::digraph("page_ordering");
+    graph.attr("rankdir", "LR").node_attr("shape", "box");
+
+    for order in orders.lines() {
+        let (b, a) = order.split_once('|')?;
+        graph.edge(b, a);
+    }
+
+    Some(graph)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
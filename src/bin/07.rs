@@ -1,81 +1,25 @@
+use advent_of_code::equation::{Add, Concat, Equation, Multiply, Operator};
+
 advent_of_code::solution!(7);
 
-fn apply_operations(
-    current_value: u64,
-    numbers: &Vec<u64>,
-    index: usize,
-    is_part_one: bool,
-) -> bool {
-    if index == 0 {
-        return numbers[0] == current_value;
-    }
-    let next_value = numbers[index];
-    if current_value % next_value == 0
-        && apply_operations(current_value / next_value, numbers, index - 1, is_part_one)
-    {
-        return true;
-    }
-    if current_value >= next_value
-        && apply_operations(current_value - next_value, numbers, index - 1, is_part_one)
-    {
-        return true;
-    }
-    if !is_part_one {
-        let digit_offset = 10u64.pow(next_value.ilog10() + 1);
-        if current_value % digit_offset == next_value
-            && apply_operations(
-                current_value / digit_offset,
-                numbers,
-                index - 1,
-                is_part_one,
-            )
-        {
-            return true;
-        }
-    }
-    false
+fn calibration_result(input: &str, operators: &[&dyn Operator]) -> u64 {
+    input
+        .lines()
+        .map(|line| line.parse::<Equation>().unwrap())
+        .filter(|equation| equation.is_solvable(operators))
+        .map(|equation| equation.target)
+        .sum()
 }
 
 pub fn part_one(input: &str) -> Option<u64> {
-    let result: u64 = input
-        .lines()
-        .filter_map(|line| {
-            let (target_string, numbers_string) = line.split_once(": ").unwrap();
-            let target = target_string.parse::<u64>().unwrap();
-            let numbers: Vec<u64> = numbers_string
-                .split_ascii_whitespace()
-                .map(|val| val.parse::<u64>().unwrap())
-                .collect();
-
-            if apply_operations(target, &numbers, numbers.len() - 1, true) {
-                Some(target)
-            } else {
-                None
-            }
-        })
-        .sum();
-    Some(result as u64)
+    Some(calibration_result(input, &[&Add, &Multiply]))
 }
 
 pub fn part_two(input: &str) -> Option<u64> {
-    let result: u64 = input
-        .lines()
-        .filter_map(|line| {
-            let (target_string, numbers_string) = line.split_once(": ").unwrap();
-            let target = target_string.parse::<u64>().unwrap();
-            let numbers: Vec<u64> = numbers_string
-                .split_ascii_whitespace()
-                .map(|val| val.parse::<u64>().unwrap())
-                .collect();
-
-            if apply_operations(target, &numbers, numbers.len() - 1, false) {
-                Some(target)
-            } else {
-                None
-            }
-        })
-        .sum();
-    Some(result as u64)
+    Some(calibration_result(
+        input,
+        &[&Add, &Multiply, &Concat::DECIMAL],
+    ))
 }
 
 #[cfg(test)]
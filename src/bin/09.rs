@@ -1,119 +1,197 @@
+use std::{cmp::Reverse, collections::BinaryHeap, fmt::Display};
+
 advent_of_code::solution!(9);
 
-pub fn part_one(input: &str) -> Option<u64> {
-    let mut empty_space = Vec::new();
-    let mut files = Vec::new();
-    let mut disk_size = 0;
-    let mut file_id = 0;
-    for (index, c) in input.chars().enumerate() {
-        let digit = char::to_digit(c, 10).unwrap() as u64;
-        let is_empty_block = index % 2 == 0;
-        match is_empty_block {
-            true => {
-                files.extend((disk_size..disk_size+digit).map(|file| (file_id, file)));
-                file_id += 1;
-            },
-            false => empty_space.extend(disk_size..disk_size+digit),
+#[derive(Debug, Eq, PartialEq, Clone, Copy)]
+struct Extent {
+    start: usize,
+    size: usize,
+}
+
+#[derive(Debug, Eq, PartialEq, Clone, Copy)]
+enum Strategy {
+    /// Moves single blocks from the end of the disk into the leftmost free block.
+    Blocks,
+    /// Moves whole files, highest id first, into the leftmost span of free space that fits them.
+    Files,
+}
+
+/// Files and free space of the disk, compacted one step at a time.
+struct DiskMap {
+    strategy: Strategy,
+    size: usize,
+    /// Extents of each file by id. The first extent is where the file started, any further
+    /// extents are blocks moved away from its end.
+    files: Vec<Vec<Extent>>,
+    /// Free spans ordered by start, used by `Strategy::Blocks`.
+    free: Vec<Extent>,
+    next_free: usize,
+    /// Starts of free spans by their size, used by `Strategy::Files`.
+    free_by_size: [BinaryHeap<Reverse<usize>>; 10],
+    /// Number of files that have not been handled yet, highest id first.
+    remaining: usize,
+}
+
+impl DiskMap {
+    fn new(input: &str, strategy: Strategy) -> Self {
+        let mut files = Vec::new();
+        let mut free = Vec::new();
+        let mut free_by_size: [BinaryHeap<Reverse<usize>>; 10] = Default::default();
+        let mut size = 0;
+
+        for (index, c) in input.trim().chars().enumerate() {
+            let digit = char::to_digit(c, 10).unwrap() as usize;
+            let extent = Extent {
+                start: size,
+                size: digit,
+            };
+            if index % 2 == 0 {
+                files.push(vec![extent]);
+            } else if digit > 0 {
+                free.push(extent);
+                free_by_size[digit].push(Reverse(size));
+            }
+            size += digit;
+        }
+
+        DiskMap {
+            strategy,
+            size,
+            remaining: files.len(),
+            files,
+            free,
+            next_free: 0,
+            free_by_size,
         }
-        disk_size += digit;
     }
 
-    let mut pointer = files.len() - 1;
-    for index in &empty_space {
-        let current_file = &files[pointer];
-        if index > &current_file.1 {
-            break;
+    /// Moves the next block or file. Returns `false` once the disk is compacted.
+    fn step(&mut self) -> bool {
+        match self.strategy {
+            Strategy::Blocks => self.move_block(),
+            Strategy::Files => self.move_file(),
         }
-        files[pointer] = (current_file.0, *index);
-        pointer -= 1;
     }
 
-    let mut checksum = 0;
-    for file in &files {
-        checksum += file.0 * file.1;
+    fn compact(&mut self) {
+        while self.step() {}
     }
-    Some(checksum)
-}
 
-#[derive(Debug, Eq, Hash, PartialEq, Clone, Copy)]
-struct FileBlock {
-    size: u64,
-    id: u64,
-    start_index: u64,
-}
+    fn move_block(&mut self) -> bool {
+        while self.remaining > 0 {
+            let id = self.remaining - 1;
+            let tail = self.files[id][0];
+            if tail.size == 0 {
+                self.remaining -= 1;
+                continue;
+            }
 
-impl FileBlock {
-    fn new(file_size: u64, file_id: u64, start_index: u64) -> Self {
-        FileBlock {
-            size: file_size,
-            id: file_id,
-            start_index,
-        }
-    }
-}
+            let Some(gap) = self.free.get_mut(self.next_free) else {
+                return false;
+            };
+            if gap.start >= tail.start {
+                return false;
+            }
 
-#[derive(Debug, Eq, Hash, PartialEq, Clone, Copy)]
-struct EmptyBlock {
-    size: u64,
-    start_index: u64,
-}
+            let target = gap.start;
+            gap.start += 1;
+            gap.size -= 1;
+            if gap.size == 0 {
+                self.next_free += 1;
+            }
 
-impl EmptyBlock {
-    fn new(empty_block_size: u64, start_index: u64) -> Self {
-        EmptyBlock {
-            size: empty_block_size,
-            start_index,
+            let extents = &mut self.files[id];
+            extents[0].size -= 1;
+            match extents[1..].last_mut() {
+                Some(last) if last.start + last.size == target => last.size += 1,
+                _ => extents.push(Extent {
+                    start: target,
+                    size: 1,
+                }),
+            }
+            return true;
         }
+        false
     }
-}
 
-pub fn part_two(input: &str) -> Option<u64> {
-    let mut empty_space = Vec::new();
-    let mut files = Vec::new();
-    let mut disk_size = 0;
-    let mut file_id = 0;
-    for (index, c) in input.chars().enumerate() {
-        let digit = char::to_digit(c, 10).unwrap() as u64;
-        let is_empty_block = index % 2 == 0;
-        match is_empty_block {
-            true => {
-                files.push(FileBlock::new(digit, file_id, disk_size));
-                file_id += 1;
-            },
-            false => empty_space.push(EmptyBlock::new(digit, disk_size)),
+    fn move_file(&mut self) -> bool {
+        while self.remaining > 0 {
+            self.remaining -= 1;
+            let file = &mut self.files[self.remaining][0];
+
+            // leftmost span among all sizes the file fits into.
+            let Some((gap_size, Reverse(gap_start))) = (file.size..10)
+                .filter_map(|size| Some((size, *self.free_by_size[size].peek()?)))
+                .min_by_key(|&(_, Reverse(start))| start)
+            else {
+                continue;
+            };
+            if gap_start >= file.start {
+                continue;
+            }
+
+            self.free_by_size[gap_size].pop();
+            if gap_size > file.size {
+                self.free_by_size[gap_size - file.size].push(Reverse(gap_start + file.size));
+            }
+            file.start = gap_start;
+            return true;
         }
-        disk_size += digit;
+        false
     }
 
-    for file in files.iter_mut().rev() {
-        if let Some(empty_block) = empty_space.iter_mut()
-            .find(|block| block.start_index <= file.start_index && block.size >= file.size)
-        {
-            file.start_index = empty_block.start_index;
-
-            empty_block.start_index += file.size;
-            empty_block.size -= file.size;
-            if empty_block.size == 0 {
-                empty_space.retain(|block| block.size > 0);
+    fn checksum(&self) -> u64 {
+        let mut checksum = 0;
+        for (id, extents) in self.files.iter().enumerate() {
+            for extent in extents {
+                for position in extent.start..extent.start + extent.size {
+                    checksum += (id * position) as u64;
+                }
             }
         }
+        checksum
     }
+}
 
-    let mut checksum = 0;
-    for file in &files {
-        for i in 0..file.size {
-            checksum += file.id * (file.start_index + i)
+/// Renders the disk like the puzzle, e.g. `0..111....22222`. Ids above 9 show their last digit.
+impl Display for DiskMap {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut blocks = vec!['.'; self.size];
+        for (id, extents) in self.files.iter().enumerate() {
+            let digit = char::from_digit((id % 10) as u32, 10).unwrap();
+            for extent in extents {
+                blocks[extent.start..extent.start + extent.size].fill(digit);
+            }
         }
+        write!(f, "{}", blocks.iter().collect::<String>())
     }
-    Some(checksum as u64)
 }
 
+pub fn part_one(input: &str) -> Option<u64> {
+    let mut disk = DiskMap::new(input, Strategy::Blocks);
+    disk.compact();
+    Some(disk.checksum())
+}
 
+pub fn part_two(input: &str) -> Option<u64> {
+    let mut disk = DiskMap::new(input, Strategy::Files);
+    disk.compact();
+    Some(disk.checksum())
+}
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    fn steps(input: &str, strategy: Strategy) -> Vec<String> {
+        let mut disk = DiskMap::new(input, strategy);
+        let mut steps = vec![disk.to_string()];
+        while disk.step() {
+            steps.push(disk.to_string());
+        }
+        steps
+    }
+
     #[test]
     fn test_part_one() {
         let result = part_one(&advent_of_code::template::read_file("examples", DAY));
@@ -125,4 +203,40 @@ mod tests {
         let result = part_two(&advent_of_code::template::read_file("examples", DAY));
         assert_eq!(result, Some(2858));
     }
+
+    #[test]
+    fn test_block_steps() {
+        assert_eq!(
+            steps("12345", Strategy::Blocks),
+            [
+                "0..111....22222",
+                "02.111....2222.",
+                "022111....222..",
+                "0221112...22...",
+                "02211122..2....",
+                "022111222......",
+            ]
+        );
+
+        let steps = steps("2333133121414131402", Strategy::Blocks);
+        assert_eq!(steps[1], "009..111...2...333.44.5555.6666.777.88889.");
+        assert_eq!(
+            steps.last().unwrap(),
+            "0099811188827773336446555566.............."
+        );
+    }
+
+    #[test]
+    fn test_file_steps() {
+        assert_eq!(
+            steps("2333133121414131402", Strategy::Files),
+            [
+                "00...111...2...333.44.5555.6666.777.888899",
+                "0099.111...2...333.44.5555.6666.777.8888..",
+                "0099.1117772...333.44.5555.6666.....8888..",
+                "0099.111777244.333....5555.6666.....8888..",
+                "00992111777.44.333....5555.6666.....8888..",
+            ]
+        );
+    }
 }
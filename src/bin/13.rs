@@ -1,36 +1,32 @@
+use advent_of_code::linear::cheapest_combination;
 use regex::Regex;
 
 advent_of_code::solution!(13);
 
+/// Offset of the prize coordinates in part two.
+const PRIZE_OFFSET: i64 = 10000000000000;
+
 struct Machine {
-    a_x: i32,
-    a_y: i32,
-    b_x: i32,
-    b_y: i32,
-    prize_x: i32,
-    prize_y: i32,
+    a_x: i64,
+    a_y: i64,
+    b_x: i64,
+    b_y: i64,
+    prize_x: i64,
+    prize_y: i64,
 }
 
 impl Machine {
-    fn solve(self, part_one: bool) -> Option<(f64, f64)> {
-        let a = self.a_x as f64;
-        let b = self.b_x as f64;
-        let c = self.a_y as f64;
-        let d = self.b_y as f64;
-        let determinant = a * d - b * c;
-        if determinant == 0.0 {
-            return None;
-        }
-        let mut prize_x = self.prize_x as f64;
-        let mut prize_y = self.prize_y as f64;
-        if !part_one {
-            prize_x += 10000000000000.0;
-            prize_y += 10000000000000.0;
-        }
-
-        let a_clicks = (d * prize_x as f64 - (b * prize_y as f64)) / determinant;
-        let b_clicks = (-1.0 * c * prize_x as f64 + (a * prize_y as f64)) / determinant;
-        Some((a_clicks, b_clicks))
+    /// Tokens needed to win the prize, where a press of A costs 3 and one of B costs 1.
+    fn tokens(&self, part_one: bool) -> Option<u64> {
+        let offset = if part_one { 0 } else { PRIZE_OFFSET };
+        let [a_clicks, b_clicks] = cheapest_combination(
+            [self.a_x, self.a_y],
+            [self.b_x, self.b_y],
+            [self.prize_x + offset, self.prize_y + offset],
+            [3, 1],
+        )
+        .unwrap()?;
+        Some(3 * a_clicks as u64 + b_clicks as u64)
     }
 }
 
@@ -46,10 +42,8 @@ pub fn parse_input(input: &str, part_one: bool) -> u64 {
             prize_x: captures["prize_x"].parse().unwrap(),
             prize_y: captures["prize_y"].parse().unwrap(),
         };
-        if let Some((a_clicks, b_clicks)) = machine.solve(part_one) {
-            if a_clicks.fract() == 0.0 && b_clicks.fract() == 0.0 {
-                result += 3 * a_clicks as u64 + b_clicks as u64;
-            }
+        if let Some(tokens) = machine.tokens(part_one) {
+            result += tokens;
         }
     }
     result
@@ -76,6 +70,6 @@ mod tests {
     #[test]
     fn test_part_two() {
         let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, None);
+        assert_eq!(result, Some(875318608908));
     }
 }
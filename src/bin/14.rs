@@ -1,7 +1,13 @@
-advent_of_code::solution!(14);
+use advent_of_code::frame::Frame;
+
+advent_of_code::solution!(14, frames = export_frames);
 
 const BOARD_HEIGHT: i32 = 103;
 const BOARD_WIDTH: i32 = 101;
+/// Positions repeat after this many seconds, as both board sizes are prime.
+const PERIOD: i32 = BOARD_WIDTH * BOARD_HEIGHT;
+/// Number of best scoring frames written by `--frames`.
+const EXPORTED_FRAMES: usize = 5;
 
 #[derive(Debug, Eq, Hash, PartialEq, Clone, Copy)]
 struct Position(i32, i32);
@@ -29,6 +35,171 @@ impl Robot {
         self.position.0 = wrap_around(self.position.0 + self.velocity.0, BOARD_WIDTH);
         self.position.1 = wrap_around(self.position.1 + self.velocity.1, BOARD_HEIGHT);
     }
+
+    /// Position after `seconds`, without simulating the ticks in between.
+    fn position_at(&self, seconds: i32) -> Position {
+        Position(
+            (self.position.0 + self.velocity.0 * seconds).rem_euclid(BOARD_WIDTH),
+            (self.position.1 + self.velocity.1 * seconds).rem_euclid(BOARD_HEIGHT),
+        )
+    }
+}
+
+fn positions_at(robots: &[Robot], seconds: i32) -> Vec<Position> {
+    robots
+        .iter()
+        .map(|robot| robot.position_at(seconds))
+        .collect()
+}
+
+fn safety_factor(positions: &[Position]) -> u32 {
+    let middle_x = BOARD_WIDTH / 2;
+    let middle_y = BOARD_HEIGHT / 2;
+    let mut quadrant = (0, 0, 0, 0);
+    for position in positions {
+        match (position.0, position.1) {
+            (x, y) if x < middle_x && y < middle_y => quadrant.0 += 1,
+            (x, y) if x < middle_x && y > middle_y => quadrant.1 += 1,
+            (x, y) if x > middle_x && y < middle_y => quadrant.2 += 1,
+            (x, y) if x > middle_x && y > middle_y => quadrant.3 += 1,
+            _ => {}
+        }
+    }
+    quadrant.0 * quadrant.1 * quadrant.2 * quadrant.3
+}
+
+/// Ways to spot the frame with the Christmas tree, which is the frame with the lowest score.
+#[derive(Debug, Clone, Copy)]
+enum Heuristic {
+    /// Robots cluster in one quadrant, which lowers the product of the quadrant counts.
+    SafetyFactor,
+    /// Robots cluster in few cells of a coarse grid, which lowers the entropy of the counts.
+    Entropy,
+    /// The tree has long horizontal lines of robots.
+    HorizontalRun,
+}
+
+impl Heuristic {
+    fn score(self, positions: &[Position]) -> f64 {
+        match self {
+            Heuristic::SafetyFactor => f64::from(safety_factor(positions)),
+            Heuristic::Entropy => {
+                const CELL: i32 = 8;
+                let columns = (BOARD_WIDTH + CELL - 1) / CELL;
+                let mut counts =
+                    vec![0u32; (columns * ((BOARD_HEIGHT + CELL - 1) / CELL)) as usize];
+                for position in positions {
+                    counts[((position.1 / CELL) * columns + position.0 / CELL) as usize] += 1;
+                }
+
+                let total = positions.len() as f64;
+                counts
+                    .iter()
+                    .filter(|&&count| count > 0)
+                    .map(|&count| {
+                        let p = f64::from(count) / total;
+                        -p * p.log2()
+                    })
+                    .sum()
+            }
+            Heuristic::HorizontalRun => {
+                let mut occupied = vec![false; (BOARD_WIDTH * BOARD_HEIGHT) as usize];
+                for position in positions {
+                    occupied[(position.1 * BOARD_WIDTH + position.0) as usize] = true;
+                }
+
+                let longest = occupied
+                    .chunks(BOARD_WIDTH as usize)
+                    .flat_map(|row| row.split(|&set| !set).map(<[bool]>::len))
+                    .max()
+                    .unwrap_or(0);
+                -(longest as f64)
+            }
+        }
+    }
+}
+
+/// Variance of one coordinate, which is lowest when the robots cluster on that axis.
+fn variance(values: impl Iterator<Item = i32> + Clone) -> f64 {
+    let count = values.clone().count() as f64;
+    let mean = values.clone().map(f64::from).sum::<f64>() / count;
+    values.map(|v| (f64::from(v) - mean).powi(2)).sum::<f64>() / count
+}
+
+/// Frame in which both axes cluster. x repeats every `BOARD_WIDTH` seconds and y every
+/// `BOARD_HEIGHT` seconds, so the best offset of each axis is combined with the CRT.
+fn crt_frame(robots: &[Robot]) -> i32 {
+    let best_offset = |period: i32, axis: fn(&Position) -> i32| {
+        let scores: Vec<(i32, f64)> = (0..period)
+            .map(|t| {
+                let positions = robots.iter().map(move |r| axis(&r.position_at(t)));
+                (t, variance(positions))
+            })
+            .collect();
+        scores
+            .into_iter()
+            .min_by(|a, b| a.1.total_cmp(&b.1))
+            .unwrap()
+            .0
+    };
+
+    let x = best_offset(BOARD_WIDTH, |p| p.0);
+    let y = best_offset(BOARD_HEIGHT, |p| p.1);
+
+    (0..BOARD_HEIGHT)
+        .map(|k| x + k * BOARD_WIDTH)
+        .find(|t| t % BOARD_HEIGHT == y)
+        .unwrap()
+}
+
+/// Frames ordered by their score, best first.
+fn ranked_frames(robots: &[Robot], heuristic: Heuristic) -> Vec<i32> {
+    let mut scores: Vec<(i32, f64)> = (0..PERIOD)
+        .map(|t| (t, heuristic.score(&positions_at(robots, t))))
+        .collect();
+    scores.sort_by(|a, b| a.1.total_cmp(&b.1));
+    scores.into_iter().map(|(t, _)| t).collect()
+}
+
+/// Second at which the robots form the tree, if the heuristic and the CRT agree on it.
+fn find_tree(robots: &[Robot], heuristic: Heuristic) -> Option<i32> {
+    let frame = *ranked_frames(robots, heuristic).first()?;
+    (frame == crt_frame(robots)).then_some(frame)
+}
+
+fn to_frame(robots: &[Robot], seconds: i32) -> Frame {
+    let mut frame = Frame::new(
+        format!("frame_{seconds:05}"),
+        BOARD_WIDTH as usize,
+        BOARD_HEIGHT as usize,
+    );
+    for position in positions_at(robots, seconds) {
+        frame.set(position.0 as usize, position.1 as usize);
+    }
+    frame
+}
+
+/// The best scoring frames of each heuristic, the tree should be among them.
+fn export_frames(input: &str) -> Vec<Frame> {
+    let robots = parse_robots(input);
+    let mut seconds: Vec<i32> = vec![];
+
+    for heuristic in [
+        Heuristic::SafetyFactor,
+        Heuristic::Entropy,
+        Heuristic::HorizontalRun,
+    ] {
+        for t in ranked_frames(&robots, heuristic)
+            .into_iter()
+            .take(EXPORTED_FRAMES)
+        {
+            if !seconds.contains(&t) {
+                seconds.push(t);
+            }
+        }
+    }
+
+    seconds.into_iter().map(|t| to_frame(&robots, t)).collect()
 }
 
 fn parse_robots(input: &str) -> Vec<Robot> {
@@ -63,23 +234,13 @@ pub fn part_one(input: &str) -> Option<u32> {
             robot.tick();
         }
     }
-    let middle_x = BOARD_WIDTH / 2;
-    let middle_y = BOARD_HEIGHT / 2;
-    let mut quadrant = (0, 0, 0, 0);
-    for robot in &robots {
-        match (robot.position.0, robot.position.1) {
-            (x, y) if x < middle_x && y < middle_y => quadrant.0 += 1,
-            (x, y) if x < middle_x && y > middle_y => quadrant.1 += 1,
-            (x, y) if x > middle_x && y < middle_y => quadrant.2 += 1,
-            (x, y) if x > middle_x && y > middle_y => quadrant.3 += 1,
-            _ => {}
-        }
-    }
-    Some(quadrant.0 * quadrant.1 * quadrant.2 * quadrant.3)
+    let positions: Vec<Position> = robots.iter().map(|robot| robot.position).collect();
+    Some(safety_factor(&positions))
 }
 
 pub fn part_two(input: &str) -> Option<u32> {
-    None
+    let robots = parse_robots(input);
+    find_tree(&robots, Heuristic::SafetyFactor).map(|t| t as u32)
 }
 
 #[cfg(test)]
@@ -92,9 +253,52 @@ mod tests {
         assert_eq!(result, Some(12));
     }
 
+    /// Pseudo-random value for robot `i`, so that the robots do not move as a lattice.
+    fn scatter(i: i32, salt: i32) -> i32 {
+        let mut z = ((i * 4 + salt + 1) as u64).wrapping_mul(0x9E37_79B9_7F4A_7C15);
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        ((z ^ (z >> 31)) % 1_000_003) as i32
+    }
+
+    /// Robots of which 60 form a filled block at `seconds`, and 60 are scattered as noise.
+    fn tree_input(seconds: i32) -> String {
+        (0..120)
+            .map(|i| {
+                let (x, y) = if i < 60 {
+                    (40 + i % 10, 40 + i / 10)
+                } else {
+                    (scatter(i, 0) % BOARD_WIDTH, scatter(i, 1) % BOARD_HEIGHT)
+                };
+                let (vx, vy) = (scatter(i, 2) % BOARD_WIDTH, scatter(i, 3) % BOARD_HEIGHT);
+                let px = (x - vx * seconds).rem_euclid(BOARD_WIDTH);
+                let py = (y - vy * seconds).rem_euclid(BOARD_HEIGHT);
+                format!("p={px},{py} v={vx},{vy}")
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, None);
+        let result = part_two(&tree_input(6_512));
+        assert_eq!(result, Some(6_512));
+    }
+
+    #[test]
+    fn test_heuristics_agree() {
+        let robots = parse_robots(&tree_input(1_234));
+        assert_eq!(crt_frame(&robots), 1_234);
+
+        for heuristic in [
+            Heuristic::SafetyFactor,
+            Heuristic::Entropy,
+            Heuristic::HorizontalRun,
+        ] {
+            let tree = heuristic.score(&positions_at(&robots, 1_234));
+            for t in [0, 1_233, 5_000] {
+                assert!(tree < heuristic.score(&positions_at(&robots, t)));
+            }
+        }
     }
 }
@@ -3,7 +3,9 @@ use std::{
     collections::{BinaryHeap, HashMap, HashSet},
 };
 
-advent_of_code::solution!(16);
+use advent_of_code::dot::Graph;
+
+advent_of_code::solution!(16, dot = to_dot);
 
 #[derive(Debug, Eq, Hash, PartialEq, Clone, Copy)]
 enum Direction {
@@ -233,6 +235,93 @@ pub fn part_two(input: &str) -> Option<u32> {
     find_min_cost_tiles(grid, reindeer, end_tile)
 }
 
+/// The maze reduced to its junctions and dead ends, joined by corridors labelled with the cost
+/// of walking them.
+fn to_dot(input: &str) -> Option<Graph> {
+    const DIRECTIONS: [Direction; 4] = [
+        Direction::Up,
+        Direction::Down,
+        Direction::Left,
+        Direction::Right,
+    ];
+
+    let (grid, reindeer, end_tile) = parse_input(input);
+    let is_open = |position: Position| grid[position.0 as usize][position.1 as usize] != '#';
+    let id = |position: Position| format!("{},{}", position.0, position.1);
+
+    let mut nodes: Vec<Position> = vec![];
+    for (x, row) in grid.iter().enumerate() {
+        for (y, _) in row.iter().enumerate() {
+            let mut position = Position(x as i32, y as i32);
+            if !is_open(position) {
+                continue;
+            }
+            let exits = DIRECTIONS
+                .iter()
+                .filter(|d| is_open(position.get_next_position(d)))
+                .count();
+            if exits != 2 || position == reindeer.position || position == end_tile {
+                nodes.push(position);
+            }
+        }
+    }
+
+    let mut graph = Graph::undirected("maze");
+    graph.attr("layout", "neato").node_attr("shape", "point");
+
+    for &node in &nodes {
+        let label = if node == reindeer.position {
+            "S"
+        } else if node == end_tile {
+            "E"
+        } else {
+            ""
+        };
+        let dot_node = graph.node(id(node));
+        // pin nodes to their maze coordinates.
+        dot_node.attr("pos", format!("{},{}!", node.1, -node.0));
+        if !label.is_empty() {
+            dot_node.attr("shape", "circle").attr("label", label);
+        }
+    }
+
+    for &node in &nodes {
+        for start in DIRECTIONS {
+            let (mut position, mut direction) = (node, start);
+            if !is_open(position.get_next_position(&direction)) {
+                continue;
+            }
+
+            let (mut steps, mut turns) = (0, 0);
+            loop {
+                position = position.get_next_position(&direction);
+                steps += 1;
+                if nodes.contains(&position) {
+                    break;
+                }
+
+                // corridor tiles have exactly one way to continue.
+                let next = [direction, direction.rotate_left(), direction.rotate_right()]
+                    .into_iter()
+                    .find(|d| is_open(position.get_next_position(d)))?;
+                if next != direction {
+                    turns += 1;
+                }
+                direction = next;
+            }
+
+            // every corridor is walked from both ends, keep one of them.
+            if (node.0, node.1) < (position.0, position.1) {
+                graph
+                    .edge(id(node), id(position))
+                    .attr("label", steps + 1000 * turns);
+            }
+        }
+    }
+
+    Some(graph)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
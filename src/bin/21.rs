@@ -0,0 +1,171 @@
+use std::collections::HashMap;
+
+advent_of_code::solution!(21);
+
+#[derive(Debug, Eq, Hash, PartialEq, Clone, Copy)]
+struct Position(i32, i32);
+
+/// Key positions of the door keypad, with the gap at `Position(3, 0)`.
+///
+/// ```text
+/// 7 8 9
+/// 4 5 6
+/// 1 2 3
+///   0 A
+/// ```
+fn numeric_key(key: char) -> Position {
+    match key {
+        '7' => Position(0, 0),
+        '8' => Position(0, 1),
+        '9' => Position(0, 2),
+        '4' => Position(1, 0),
+        '5' => Position(1, 1),
+        '6' => Position(1, 2),
+        '1' => Position(2, 0),
+        '2' => Position(2, 1),
+        '3' => Position(2, 2),
+        '0' => Position(3, 1),
+        'A' => Position(3, 2),
+        _ => panic!("Unsupported numeric key: {key}"),
+    }
+}
+
+const NUMERIC_GAP: Position = Position(3, 0);
+
+/// Key positions of the robot keypads, with the gap at `Position(0, 0)`.
+///
+/// ```text
+///   ^ A
+/// < v >
+/// ```
+fn directional_key(key: char) -> Position {
+    match key {
+        '^' => Position(0, 1),
+        'A' => Position(0, 2),
+        '<' => Position(1, 0),
+        'v' => Position(1, 1),
+        '>' => Position(1, 2),
+        _ => panic!("Unsupported directional key: {key}"),
+    }
+}
+
+const DIRECTIONAL_GAP: Position = Position(0, 0);
+
+/// Directional sequences that move an arm from `from` to `to` and press it.
+/// Only straight L-shaped paths can be optimal, and paths over the gap are skipped.
+fn moves(from: Position, to: Position, gap: Position) -> Vec<String> {
+    let (rows, columns) = (to.0 - from.0, to.1 - from.1);
+    let vertical = if rows > 0 { "v" } else { "^" }.repeat(rows.unsigned_abs() as usize);
+    let horizontal = if columns > 0 { ">" } else { "<" }.repeat(columns.unsigned_abs() as usize);
+
+    let mut sequences = Vec::new();
+    if Position(from.0, to.1) != gap {
+        sequences.push(format!("{horizontal}{vertical}A"));
+    }
+    if Position(to.0, from.1) != gap {
+        sequences.push(format!("{vertical}{horizontal}A"));
+    }
+    sequences.dedup();
+    sequences
+}
+
+/// Presses needed to type `sequence` on a directional keypad that is operated through
+/// `depth` more directional keypads.
+fn sequence_cost(
+    sequence: &str,
+    depth: usize,
+    cache: &mut HashMap<(char, char, usize), u64>,
+) -> u64 {
+    if depth == 0 {
+        return sequence.len() as u64;
+    }
+
+    let mut current = 'A';
+    let mut cost = 0;
+    for key in sequence.chars() {
+        cost += press_cost(current, key, depth, cache);
+        current = key;
+    }
+    cost
+}
+
+/// Presses needed to move from `from` to `to` on a directional keypad and press it.
+fn press_cost(
+    from: char,
+    to: char,
+    depth: usize,
+    cache: &mut HashMap<(char, char, usize), u64>,
+) -> u64 {
+    if let Some(&cached_result) = cache.get(&(from, to, depth)) {
+        return cached_result;
+    }
+
+    let result = moves(directional_key(from), directional_key(to), DIRECTIONAL_GAP)
+        .iter()
+        .map(|sequence| sequence_cost(sequence, depth - 1, cache))
+        .min()
+        .unwrap();
+    cache.insert((from, to, depth), result);
+    result
+}
+
+/// Presses needed to type `code` on the door keypad through `robots` directional keypads.
+fn code_cost(code: &str, robots: usize, cache: &mut HashMap<(char, char, usize), u64>) -> u64 {
+    let mut current = 'A';
+    let mut cost = 0;
+    for key in code.chars() {
+        cost += moves(numeric_key(current), numeric_key(key), NUMERIC_GAP)
+            .iter()
+            .map(|sequence| sequence_cost(sequence, robots, cache))
+            .min()
+            .unwrap();
+        current = key;
+    }
+    cost
+}
+
+fn total_complexity(input: &str, robots: usize) -> u64 {
+    let mut cache: HashMap<(char, char, usize), u64> = HashMap::new();
+    input
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(|code| {
+            let code = code.trim();
+            let numeric_part: u64 = code.trim_end_matches('A').parse().unwrap();
+            code_cost(code, robots, &mut cache) * numeric_part
+        })
+        .sum()
+}
+
+pub fn part_one(input: &str) -> Option<u64> {
+    Some(total_complexity(input, 2))
+}
+
+pub fn part_two(input: &str) -> Option<u64> {
+    Some(total_complexity(input, 25))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part_one() {
+        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        assert_eq!(result, Some(126384));
+    }
+
+    #[test]
+    fn test_part_two() {
+        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        assert_eq!(result, Some(154115708116294));
+    }
+
+    #[test]
+    fn test_code_cost() {
+        let mut cache = HashMap::new();
+        assert_eq!(code_cost("029A", 0, &mut cache), 12);
+        assert_eq!(code_cost("029A", 1, &mut cache), 28);
+        assert_eq!(code_cost("029A", 2, &mut cache), 68);
+    }
+}
@@ -1,93 +1,52 @@
-use std::collections::{HashMap, HashSet};
+use advent_of_code::{dot, graph::Graph};
 
-advent_of_code::solution!(23);
+advent_of_code::solution!(23, dot = to_dot);
 
-pub fn part_one(input: &str) -> Option<u64> {
-    let mut graph: HashMap<String, Vec<String>> = HashMap::new();
-    input.lines().for_each(|line| {
-        let (n1, n2) = line.split_once('-').unwrap();
-        graph.entry(n1.to_string()).or_insert_with(Vec::new).push(n2.to_string());
-        graph.entry(n2.to_string()).or_insert_with(Vec::new).push(n1.to_string());
-    });
+fn parse_network(input: &str) -> Graph {
+    Graph::from_edges(input.lines().map(|line| line.split_once('-').unwrap()))
+}
 
-    let result: u64 = graph.iter().map(|(node, neighbours)| {
-        let mut triplet_count = 0;
-        let num_neighbours = neighbours.len();
-        for i in 0..num_neighbours {
-            for j in i+1..num_neighbours {
-                let n1 = &neighbours[i];
-                let n2 = &neighbours[j];
-                if !n1.starts_with('t') && !n2.starts_with('t') && !node.starts_with('t') {
-                    continue;
-                }
-                if graph.get(n1).unwrap().contains(n2) {
-                    triplet_count += 1;
-                }
-            }
-        }
-        triplet_count
-    }).sum();
+/// Computers in the LAN party, sorted by name.
+fn lan_party(network: &Graph) -> Vec<&str> {
+    let mut party: Vec<&str> = network
+        .maximum_clique()
+        .iter()
+        .map(|id| network.name(id))
+        .collect();
+    party.sort();
+    party
+}
 
-    Some(result / 3)
+pub fn part_one(input: &str) -> Option<u64> {
+    let network = parse_network(input);
+    let triangles = network.triangles(|id| network.name(id).starts_with('t'));
+    Some(triangles.len() as u64)
 }
 
-// See here: https://www.geeksforgeeks.org/maximal-clique-problem-recursive-solution/
-fn bron_kerbosch(
-    current_clique: HashSet<String>,
-    potential_nodes: HashSet<String>,
-    graph: &HashMap<String, Vec<String>>,
-    max_clique: &mut HashSet<String>,
-) {
-    if potential_nodes.is_empty() {
-        if current_clique.len() > max_clique.len() {
-            *max_clique = current_clique;
-        }
-        return;
-    }
+pub fn part_two(input: &str) -> Option<String> {
+    let network = parse_network(input);
+    Some(lan_party(&network).join(","))
+}
 
-    let mut next_potential_nodes = potential_nodes.clone();
-    for node in potential_nodes.iter() {
-        let mut next_clique = current_clique.clone();
-        next_clique.insert(node.clone());
+/// The network, with the computers of the LAN party drawn as a cluster.
+fn to_dot(input: &str) -> Option<dot::Graph> {
+    let network = parse_network(input);
 
-        let neighbors = graph
-            .get(node)
-            .unwrap()
-            .iter()
-            .cloned()
-            .collect();
+    let mut graph = dot::Graph::undirected("lan");
+    // unlike the default `dot` layout, `fdp` handles clusters in undirected graphs.
+    graph.attr("layout", "fdp");
 
-        bron_kerbosch(
-            next_clique,
-            next_potential_nodes.intersection(&neighbors).cloned().collect(),
-            graph,
-            max_clique,
-        );
-        next_potential_nodes.remove(node);
+    let cluster = graph.cluster("party");
+    cluster.attr("label", "LAN party").attr("color", "red");
+    for computer in lan_party(&network) {
+        cluster.node(computer);
     }
-}
 
-pub fn part_two(input: &str) -> Option<String> {
-    let mut graph = HashMap::new();
-    let mut node_set = HashSet::new();
-    input.lines().for_each(|line| {
-        let (n1, n2) = line.split_once('-').unwrap();
-        graph.entry(n1.to_string()).or_insert_with(Vec::new).push(n2.to_string());
-        graph.entry(n2.to_string()).or_insert_with(Vec::new).push(n1.to_string());
-        node_set.insert(n1.to_string());
-        node_set.insert(n2.to_string());
-    });
+    for (a, b) in network.edges() {
+        graph.edge(network.name(a), network.name(b));
+    }
 
-    let mut max_clique = HashSet::new();
-    bron_kerbosch(
-        HashSet::new(),
-        node_set,
-        &graph,
-        &mut max_clique,
-    );
-    let mut max_clique = max_clique.into_iter().collect::<Vec<String>>();
-    max_clique.sort();
-    Some(max_clique.join(","))
+    Some(graph)
 }
 
 #[cfg(test)]
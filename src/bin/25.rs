@@ -0,0 +1,69 @@
+advent_of_code::solution!(25, 1);
+
+/// A lock or key schematic reduced to the height of each column, not counting the base row.
+#[derive(Debug, Eq, PartialEq, Clone)]
+struct Schematic {
+    is_lock: bool,
+    heights: Vec<usize>,
+    /// Free rows between the base rows of a lock and a key.
+    space: usize,
+}
+
+impl Schematic {
+    fn new(block: &str) -> Self {
+        let rows: Vec<&[u8]> = block.lines().map(str::as_bytes).collect();
+        let is_lock = rows[0].iter().all(|&c| c == b'#');
+        let heights = (0..rows[0].len())
+            .map(|column| rows.iter().filter(|row| row[column] == b'#').count() - 1)
+            .collect();
+
+        Schematic {
+            is_lock,
+            heights,
+            space: rows.len() - 2,
+        }
+    }
+
+    fn fits(&self, other: &Schematic) -> bool {
+        self.heights
+            .iter()
+            .zip(&other.heights)
+            .all(|(a, b)| a + b <= self.space)
+    }
+}
+
+pub fn part_one(input: &str) -> Option<u32> {
+    let (locks, keys): (Vec<Schematic>, Vec<Schematic>) = input
+        .split("\n\n")
+        .filter(|block| !block.trim().is_empty())
+        .map(|block| Schematic::new(block.trim()))
+        .partition(|schematic| schematic.is_lock);
+
+    let mut result = 0;
+    for lock in &locks {
+        for key in &keys {
+            if lock.fits(key) {
+                result += 1;
+            }
+        }
+    }
+    Some(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part_one() {
+        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        assert_eq!(result, Some(3));
+    }
+
+    #[test]
+    fn test_heights() {
+        let lock = Schematic::new("#####\n.####\n.####\n.####\n.#.#.\n.#...\n.....");
+        assert!(lock.is_lock);
+        assert_eq!(lock.heights, vec![0, 5, 3, 4, 3]);
+    }
+}
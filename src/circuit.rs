@@ -0,0 +1,486 @@
+/// Module for the boolean circuits of day 24.
+/// Gates are keyed by the wire they output to, wires starting with `x`, `y` and `z` form numbers.
+use std::{
+    collections::{HashMap, VecDeque},
+    fmt::Display,
+    str::FromStr,
+};
+
+use crate::dot::Graph;
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum Error {
+    Parser(String),
+    /// A wire depends on itself, e.g. after swapping the wrong outputs.
+    Cycle,
+    /// A wire that is neither an initial state nor the output of a gate.
+    UnknownWire(String),
+    /// The adder structure of this bit could not be repaired by swapping outputs.
+    Unresolvable {
+        bit: usize,
+    },
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::Parser(e) => write!(f, "{e}"),
+            Error::Cycle => write!(f, "circuit contains a cycle"),
+            Error::UnknownWire(wire) => write!(f, "wire \"{wire}\" has no value or gate"),
+            Error::Unresolvable { bit } => write!(f, "cannot repair adder at bit {bit}"),
+        }
+    }
+}
+
+#[derive(Debug, Eq, Hash, PartialEq, Clone, Copy)]
+pub enum Operation {
+    And,
+    Xor,
+    Or,
+}
+
+impl Operation {
+    fn apply(self, a: bool, b: bool) -> bool {
+        match self {
+            Operation::And => a && b,
+            Operation::Xor => a != b,
+            Operation::Or => a || b,
+        }
+    }
+}
+
+impl Display for Operation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Operation::And => write!(f, "AND"),
+            Operation::Xor => write!(f, "XOR"),
+            Operation::Or => write!(f, "OR"),
+        }
+    }
+}
+
+#[derive(Debug, Eq, Hash, PartialEq, Clone)]
+pub struct GateInput {
+    pub input1: String,
+    pub input2: String,
+    pub operation: Operation,
+}
+
+impl GateInput {
+    fn has_inputs(&self, a: &str, b: &str) -> bool {
+        (self.input1 == a && self.input2 == b) || (self.input1 == b && self.input2 == a)
+    }
+
+    /// The input that is not `wire`, if `wire` is one of them.
+    fn other_input(&self, wire: &str) -> Option<&str> {
+        if self.input1 == wire {
+            Some(&self.input2)
+        } else if self.input2 == wire {
+            Some(&self.input1)
+        } else {
+            None
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Circuit {
+    pub states: HashMap<String, bool>,
+    pub gates: HashMap<String, GateInput>,
+}
+
+impl Circuit {
+    /// Gate outputs ordered so that every gate comes after the gates it reads from.
+    pub fn topological_order(&self) -> Result<Vec<&str>, Error> {
+        let mut pending: HashMap<&str, usize> = HashMap::new();
+        let mut readers: HashMap<&str, Vec<&str>> = HashMap::new();
+
+        for (output, gate) in &self.gates {
+            let inputs = [gate.input1.as_str(), gate.input2.as_str()];
+            for input in inputs {
+                if self.gates.contains_key(input) {
+                    *pending.entry(output).or_default() += 1;
+                    readers.entry(input).or_default().push(output);
+                } else if !self.states.contains_key(input) {
+                    return Err(Error::UnknownWire(input.to_string()));
+                }
+            }
+        }
+
+        let mut queue: VecDeque<&str> = self
+            .gates
+            .keys()
+            .map(String::as_str)
+            .filter(|output| !pending.contains_key(output))
+            .collect();
+        let mut order = Vec::with_capacity(self.gates.len());
+
+        while let Some(wire) = queue.pop_front() {
+            order.push(wire);
+
+            for &reader in readers.get(wire).into_iter().flatten() {
+                let count = pending.get_mut(reader).unwrap();
+                *count -= 1;
+                if *count == 0 {
+                    queue.push_back(reader);
+                }
+            }
+        }
+
+        if order.len() == self.gates.len() {
+            Ok(order)
+        } else {
+            Err(Error::Cycle)
+        }
+    }
+
+    /// Values of all wires after the circuit settled.
+    pub fn evaluate(&self) -> Result<HashMap<String, bool>, Error> {
+        let mut values = self.states.clone();
+
+        for wire in self.topological_order()? {
+            let gate = &self.gates[wire];
+            let value = gate
+                .operation
+                .apply(values[&gate.input1], values[&gate.input2]);
+            values.insert(wire.to_string(), value);
+        }
+
+        Ok(values)
+    }
+
+    /// Number formed by the wires starting with `prefix`, `00` being the least significant bit.
+    pub fn number(values: &HashMap<String, bool>, prefix: char) -> u64 {
+        values
+            .iter()
+            .filter_map(|(wire, value)| {
+                let bit: u32 = wire.strip_prefix(prefix)?.parse().ok()?;
+                Some(u64::from(*value) << bit)
+            })
+            .sum()
+    }
+
+    /// Output wire of the gate reading `a` and `b` with `operation`.
+    fn find(&self, a: &str, b: &str, operation: Operation) -> Option<String> {
+        self.gates
+            .iter()
+            .find(|(_, gate)| gate.operation == operation && gate.has_inputs(a, b))
+            .map(|(output, _)| output.clone())
+    }
+
+    /// Swaps the outputs of the gates driving `a` and `b`. Fails if either wire has no gate,
+    /// e.g. when it is an initial state.
+    fn swap(&mut self, a: &str, b: &str, bit: usize) -> Result<(), Error> {
+        if !self.gates.contains_key(a) || !self.gates.contains_key(b) {
+            return Err(Error::Unresolvable { bit });
+        }
+
+        let gate_a = self.gates.remove(a).unwrap();
+        let gate_b = self.gates.insert(b.to_string(), gate_a).unwrap();
+        self.gates.insert(a.to_string(), gate_b);
+        Ok(())
+    }
+
+    /// Verifies the circuit as a ripple-carry adder bit by bit and repairs it by swapping gate
+    /// outputs. Returns the swapped wires, sorted.
+    ///
+    /// Bit `i` is expected to be built as follows, with `carry` coming from the previous bit:
+    /// `sum = xi XOR yi`, `zi = sum XOR carry`, and
+    /// `next carry = (xi AND yi) OR (sum AND carry)`.
+    pub fn find_swapped_wires(&self) -> Result<Vec<String>, Error> {
+        let mut circuit = self.clone();
+        let mut swapped: Vec<String> = vec![];
+
+        let bits = self.states.keys().filter(|w| w.starts_with('x')).count();
+        let wire = |prefix: char, bit: usize| format!("{prefix}{bit:02}");
+        let unresolvable = |bit: usize| Error::Unresolvable { bit };
+
+        // bit 0 is a half adder.
+        let z = circuit
+            .find("x00", "y00", Operation::Xor)
+            .ok_or(unresolvable(0))?;
+        if z != "z00" {
+            circuit.swap(&z, "z00", 0)?;
+            swapped.extend([z, "z00".into()]);
+        }
+        let mut carry = circuit
+            .find("x00", "y00", Operation::And)
+            .ok_or(unresolvable(0))?;
+
+        for bit in 1..bits {
+            let (x, y, z) = (wire('x', bit), wire('y', bit), wire('z', bit));
+
+            // every repair fixes one swap, a correct bit needs none.
+            let mut attempts = 0;
+            loop {
+                attempts += 1;
+                if attempts > 3 {
+                    return Err(unresolvable(bit));
+                }
+
+                let sum = circuit
+                    .find(&x, &y, Operation::Xor)
+                    .ok_or(unresolvable(bit))?;
+
+                match circuit.find(&sum, &carry, Operation::Xor) {
+                    Some(output) if output == z => {}
+                    Some(output) => {
+                        circuit.swap(&output, &z, bit)?;
+                        swapped.extend([output, z.clone()]);
+                        continue;
+                    }
+                    None => {
+                        // `zi` reads the right sum or carry, the other input is swapped.
+                        let gate = circuit.gates.get(&z).ok_or(unresolvable(bit))?;
+                        let (wrong, right) = if let Some(other) = gate.other_input(&carry) {
+                            (sum, other.to_string())
+                        } else if let Some(other) = gate.other_input(&sum) {
+                            (carry.clone(), other.to_string())
+                        } else {
+                            return Err(unresolvable(bit));
+                        };
+
+                        circuit.swap(&wrong, &right, bit)?;
+                        if wrong == carry {
+                            carry.clone_from(&right);
+                        }
+                        swapped.extend([wrong, right]);
+                        continue;
+                    }
+                }
+
+                let generate = circuit
+                    .find(&x, &y, Operation::And)
+                    .ok_or(unresolvable(bit))?;
+                let propagate = circuit
+                    .find(&sum, &carry, Operation::And)
+                    .ok_or(unresolvable(bit))?;
+                carry = circuit
+                    .find(&generate, &propagate, Operation::Or)
+                    .ok_or(unresolvable(bit))?;
+                break;
+            }
+        }
+
+        // the final carry is the most significant output bit.
+        let last = wire('z', bits);
+        if carry != last {
+            circuit.swap(&carry, &last, bits)?;
+            swapped.extend([carry, last]);
+        }
+
+        swapped.sort_unstable();
+        Ok(swapped)
+    }
+
+    /// Graph of the circuit with a node per gate and wire. Inputs and outputs are clustered.
+    pub fn to_dot(&self) -> Graph {
+        let mut graph = Graph::digraph("circuit");
+        graph.attr("rankdir", "LR");
+
+        let mut inputs: Vec<&String> = self.states.keys().collect();
+        inputs.sort_unstable();
+        let mut outputs: Vec<&String> = self.gates.keys().collect();
+        outputs.sort_unstable();
+
+        let cluster = graph.cluster("inputs");
+        cluster.attr("label", "inputs");
+        for wire in inputs {
+            cluster.node(wire).attr("shape", "ellipse");
+        }
+
+        let cluster = graph.cluster("outputs");
+        cluster.attr("label", "outputs");
+        for wire in outputs.iter().filter(|wire| wire.starts_with('z')) {
+            cluster.node(wire).attr("shape", "ellipse");
+        }
+
+        for output in outputs {
+            let gate = &self.gates[output];
+            let node = format!("gate_{output}");
+            graph
+                .node(&node)
+                .attr("shape", "polygon")
+                .attr("sides", 6)
+                .attr("label", gate.operation);
+            graph.edge(&gate.input1, &node);
+            graph.edge(&gate.input2, &node);
+            graph.edge(&node, output);
+        }
+
+        graph
+    }
+}
+
+impl FromStr for Circuit {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (states_str, gates_str) = s
+            .split_once("\n\n")
+            .ok_or_else(|| Error::Parser("expected states and gates".into()))?;
+
+        let mut states = HashMap::new();
+        for line in states_str.lines() {
+            let (wire, value) = line
+                .split_once(": ")
+                .ok_or_else(|| Error::Parser(format!("unexpected state \"{line}\"")))?;
+            let value = match value.trim() {
+                "0" => false,
+                "1" => true,
+                _ => return Err(Error::Parser(format!("\"{value}\" is not a bit"))),
+            };
+            states.insert(wire.to_string(), value);
+        }
+
+        let mut gates = HashMap::new();
+        for line in gates_str.lines().filter(|line| !line.trim().is_empty()) {
+            let [input1, operation, input2, "->", output] =
+                line.split_whitespace().collect::<Vec<_>>()[..]
+            else {
+                return Err(Error::Parser(format!("unexpected gate \"{line}\"")));
+            };
+
+            let operation = match operation {
+                "AND" => Operation::And,
+                "XOR" => Operation::Xor,
+                "OR" => Operation::Or,
+                _ => return Err(Error::Parser(format!("unknown operation \"{operation}\""))),
+            };
+
+            gates.insert(
+                output.to_string(),
+                GateInput {
+                    input1: input1.to_string(),
+                    input2: input2.to_string(),
+                    operation,
+                },
+            );
+        }
+
+        Ok(Circuit { states, gates })
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Circuit, Error};
+
+    /// Ripple-carry adder over `bits` bits computing `x + y`.
+    fn adder(bits: usize, x: u64, y: u64) -> String {
+        let mut lines = vec![];
+        for bit in 0..bits {
+            lines.push(format!("x{bit:02}: {}", (x >> bit) & 1));
+        }
+        for bit in 0..bits {
+            lines.push(format!("y{bit:02}: {}", (y >> bit) & 1));
+        }
+        lines.push(String::new());
+
+        lines.push("x00 XOR y00 -> z00".into());
+        lines.push("x00 AND y00 -> c00".into());
+        for bit in 1..bits {
+            let carry = if bit == bits - 1 {
+                format!("z{bits:02}")
+            } else {
+                format!("c{bit:02}")
+            };
+            let prev = format!("c{:02}", bit - 1);
+            lines.push(format!("x{bit:02} XOR y{bit:02} -> s{bit:02}"));
+            lines.push(format!("x{bit:02} AND y{bit:02} -> a{bit:02}"));
+            lines.push(format!("s{bit:02} XOR {prev} -> z{bit:02}"));
+            lines.push(format!("s{bit:02} AND {prev} -> b{bit:02}"));
+            lines.push(format!("a{bit:02} OR b{bit:02} -> {carry}"));
+        }
+
+        lines.join("\n")
+    }
+
+    fn swap_outputs(input: &str, a: &str, b: &str) -> String {
+        input
+            .lines()
+            .map(|line| match line.split_once(" -> ") {
+                Some((gate, output)) if output == a => format!("{gate} -> {b}"),
+                Some((gate, output)) if output == b => format!("{gate} -> {a}"),
+                _ => line.to_string(),
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    #[test]
+    fn evaluates_circuit() {
+        let circuit: Circuit = adder(4, 11, 6).parse().unwrap();
+        let values = circuit.evaluate().unwrap();
+        assert_eq!(Circuit::number(&values, 'z'), 17);
+    }
+
+    #[test]
+    fn verifies_correct_adder() {
+        let circuit: Circuit = adder(6, 0, 0).parse().unwrap();
+        assert_eq!(circuit.find_swapped_wires(), Ok(vec![]));
+    }
+
+    #[test]
+    fn detects_swapped_wires() {
+        let input = swap_outputs(&adder(6, 0, 0), "z01", "c01");
+        let input = swap_outputs(&input, "s03", "a03");
+        let circuit: Circuit = input.parse().unwrap();
+        assert_eq!(
+            circuit.find_swapped_wires().unwrap().join(","),
+            "a03,c01,s03,z01"
+        );
+    }
+
+    #[test]
+    fn rejects_malformed_adders() {
+        let input = swap_outputs(&adder(6, 0, 0), "z06", "q06");
+        let circuit: Circuit = input.parse().unwrap();
+        assert_eq!(
+            circuit.find_swapped_wires(),
+            Err(Error::Unresolvable { bit: 6 })
+        );
+
+        let input = adder(6, 0, 0).replace("s02 XOR c01 -> z02", "x02 XOR c01 -> z02");
+        let circuit: Circuit = input.parse().unwrap();
+        assert_eq!(
+            circuit.find_swapped_wires(),
+            Err(Error::Unresolvable { bit: 2 })
+        );
+    }
+
+    #[test]
+    fn detects_cycles() {
+        let input = "x00: 1\n\nx00 AND b -> a\nx00 OR a -> b";
+        let circuit: Circuit = input.parse().unwrap();
+        assert_eq!(circuit.evaluate(), Err(Error::Cycle));
+    }
+
+    #[test]
+    fn exports_dot() {
+        let circuit: Circuit = "x00: 1\ny00: 0\n\nx00 XOR y00 -> z00".parse().unwrap();
+        assert_eq!(
+            circuit.to_dot().to_string(),
+            [
+                "digraph circuit {",
+                "    rankdir=LR;",
+                "    subgraph cluster_inputs {",
+                "        label=inputs;",
+                "        x00 [shape=ellipse];",
+                "        y00 [shape=ellipse];",
+                "    }",
+                "    subgraph cluster_outputs {",
+                "        label=outputs;",
+                "        z00 [shape=ellipse];",
+                "    }",
+                "    gate_z00 [shape=polygon, sides=6, label=XOR];",
+                "    x00 -> gate_z00;",
+                "    y00 -> gate_z00;",
+                "    gate_z00 -> z00;",
+                "}",
+                "",
+            ]
+            .join("\n")
+        );
+    }
+}
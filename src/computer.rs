@@ -0,0 +1,452 @@
+/// Module for the 3-bit computer of day 17.
+/// Programs are sequences of 3-bit opcodes and operands operating on three registers.
+use std::{fmt::Display, str::FromStr};
+
+/// Steps after which a program is considered to be running away.
+pub const DEFAULT_STEP_LIMIT: usize = 1_000_000;
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum Error {
+    Parser(String),
+    /// Combo operand 7 is reserved and does not appear in valid programs.
+    ReservedOperand {
+        ip: usize,
+    },
+    StepLimit(usize),
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::Parser(e) => write!(f, "{e}"),
+            Error::ReservedOperand { ip } => write!(f, "reserved combo operand at {ip}"),
+            Error::StepLimit(limit) => write!(f, "program did not halt within {limit} steps"),
+        }
+    }
+}
+
+/// Operand whose value is either a literal or the content of a register.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Combo {
+    Literal(u8),
+    A,
+    B,
+    C,
+}
+
+impl Combo {
+    fn decode(operand: u8) -> Option<Self> {
+        match operand {
+            0..=3 => Some(Combo::Literal(operand)),
+            4 => Some(Combo::A),
+            5 => Some(Combo::B),
+            6 => Some(Combo::C),
+            _ => None,
+        }
+    }
+}
+
+impl Display for Combo {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Combo::Literal(value) => write!(f, "{value}"),
+            Combo::A => write!(f, "a"),
+            Combo::B => write!(f, "b"),
+            Combo::C => write!(f, "c"),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Instruction {
+    /// `a = a >> combo`
+    Adv(Combo),
+    /// `b = b ^ literal`
+    Bxl(u8),
+    /// `b = combo % 8`
+    Bst(Combo),
+    /// Jumps to `literal` if `a` is not zero.
+    Jnz(u8),
+    /// `b = b ^ c`
+    Bxc,
+    /// Outputs `combo % 8`.
+    Out(Combo),
+    /// `b = a >> combo`
+    Bdv(Combo),
+    /// `c = a >> combo`
+    Cdv(Combo),
+}
+
+impl Instruction {
+    /// Decodes an opcode and its operand. Returns `None` for the reserved combo operand.
+    pub fn decode(opcode: u8, operand: u8) -> Option<Self> {
+        let combo = Combo::decode(operand);
+
+        match opcode {
+            0 => combo.map(Instruction::Adv),
+            1 => Some(Instruction::Bxl(operand)),
+            2 => combo.map(Instruction::Bst),
+            3 => Some(Instruction::Jnz(operand)),
+            4 => Some(Instruction::Bxc),
+            5 => combo.map(Instruction::Out),
+            6 => combo.map(Instruction::Bdv),
+            7 => combo.map(Instruction::Cdv),
+            _ => None,
+        }
+    }
+}
+
+/// Renders the instruction as pseudo-code.
+impl Display for Instruction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Instruction::Adv(combo) => write!(f, "a = a >> {combo}"),
+            Instruction::Bxl(literal) => write!(f, "b = b ^ {literal}"),
+            Instruction::Bst(combo) => write!(f, "b = {combo} % 8"),
+            Instruction::Jnz(literal) => write!(f, "if a != 0 goto {literal}"),
+            Instruction::Bxc => write!(f, "b = b ^ c"),
+            Instruction::Out(combo) => write!(f, "out {combo} % 8"),
+            Instruction::Bdv(combo) => write!(f, "b = a >> {combo}"),
+            Instruction::Cdv(combo) => write!(f, "c = a >> {combo}"),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Registers {
+    pub a: u64,
+    pub b: u64,
+    pub c: u64,
+}
+
+impl Registers {
+    fn combo(&self, combo: Combo) -> u64 {
+        match combo {
+            Combo::Literal(value) => u64::from(value),
+            Combo::A => self.a,
+            Combo::B => self.b,
+            Combo::C => self.c,
+        }
+    }
+}
+
+/// Result of executing a single instruction.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Step {
+    Continue,
+    Output(u8),
+    Halt,
+}
+
+/// Registers before an instruction was executed, passed to the tracer.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Trace {
+    pub ip: usize,
+    pub instruction: Instruction,
+    pub registers: Registers,
+}
+
+impl Display for Trace {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let Registers { a, b, c } = self.registers;
+        let instruction = self.instruction.to_string();
+        write!(f, "{:>3}: {instruction:<20} a={a} b={b} c={c}", self.ip)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Computer {
+    pub registers: Registers,
+    program: Vec<u8>,
+    ip: usize,
+    output: Vec<u8>,
+    steps: usize,
+    step_limit: usize,
+}
+
+impl Computer {
+    pub fn new(registers: Registers, program: Vec<u8>) -> Self {
+        Computer {
+            registers,
+            program,
+            ip: 0,
+            output: vec![],
+            steps: 0,
+            step_limit: DEFAULT_STEP_LIMIT,
+        }
+    }
+
+    /// Stops programs with `Error::StepLimit` after `limit` instructions.
+    #[must_use]
+    pub fn with_step_limit(self, limit: usize) -> Self {
+        Computer {
+            step_limit: limit,
+            ..self
+        }
+    }
+
+    pub fn program(&self) -> &[u8] {
+        &self.program
+    }
+
+    pub fn output(&self) -> &[u8] {
+        &self.output
+    }
+
+    /// Output formatted as in the puzzle, e.g. `4,6,3,5`.
+    pub fn output_string(&self) -> String {
+        self.output
+            .iter()
+            .map(u8::to_string)
+            .collect::<Vec<_>>()
+            .join(",")
+    }
+
+    /// Decodes the instruction at the instruction pointer. Returns `None` once the program halts.
+    pub fn current(&self) -> Option<Result<Instruction, Error>> {
+        let (opcode, operand) = (self.program.get(self.ip)?, self.program.get(self.ip + 1)?);
+        Some(Instruction::decode(*opcode, *operand).ok_or(Error::ReservedOperand { ip: self.ip }))
+    }
+
+    pub fn step(&mut self) -> Result<Step, Error> {
+        let Some(instruction) = self.current() else {
+            return Ok(Step::Halt);
+        };
+        let instruction = instruction?;
+
+        if self.steps >= self.step_limit {
+            return Err(Error::StepLimit(self.step_limit));
+        }
+        self.steps += 1;
+
+        let registers = &mut self.registers;
+        let mut step = Step::Continue;
+
+        match instruction {
+            Instruction::Adv(combo) => registers.a = shr(registers.a, registers.combo(combo)),
+            Instruction::Bxl(literal) => registers.b ^= u64::from(literal),
+            Instruction::Bst(combo) => registers.b = registers.combo(combo) % 8,
+            Instruction::Jnz(literal) => {
+                if registers.a != 0 {
+                    self.ip = usize::from(literal);
+                    return Ok(step);
+                }
+            }
+            Instruction::Bxc => registers.b ^= registers.c,
+            Instruction::Out(combo) => {
+                #[allow(clippy::cast_possible_truncation)]
+                let value = (registers.combo(combo) % 8) as u8;
+                self.output.push(value);
+                step = Step::Output(value);
+            }
+            Instruction::Bdv(combo) => registers.b = shr(registers.a, registers.combo(combo)),
+            Instruction::Cdv(combo) => registers.c = shr(registers.a, registers.combo(combo)),
+        }
+
+        self.ip += 2;
+        Ok(step)
+    }
+
+    /// Runs the program until it halts and returns its output.
+    pub fn run(&mut self) -> Result<&[u8], Error> {
+        self.run_traced(|_| {})
+    }
+
+    /// Runs the program until it halts, calling `trace` before each instruction.
+    pub fn run_traced(&mut self, mut trace: impl FnMut(&Trace)) -> Result<&[u8], Error> {
+        while let Some(instruction) = self.current() {
+            trace(&Trace {
+                ip: self.ip,
+                instruction: instruction?,
+                registers: self.registers,
+            });
+            self.step()?;
+        }
+
+        Ok(&self.output)
+    }
+
+    /// Prints the program as pseudo-code, one instruction per line.
+    pub fn disassemble(&self) -> String {
+        self.program
+            .chunks_exact(2)
+            .enumerate()
+            .map(|(i, chunk)| {
+                let ip = i * 2;
+                match Instruction::decode(chunk[0], chunk[1]) {
+                    Some(instruction) => format!("{ip:>3}: {instruction}"),
+                    None => format!("{ip:>3}: <reserved operand {}>", chunk[1]),
+                }
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    /// Lowest value of register `a` for which the program outputs `target`.
+    ///
+    /// Assumes the usual shape of these programs: a single loop that outputs one value per
+    /// iteration and shifts `a` right by three bits. The value is then built three bits at a
+    /// time, starting with the last output.
+    pub fn find_register_a(&self, target: &[u8]) -> Option<u64> {
+        self.search(target, target.len(), 0)
+    }
+
+    /// Lowest value of register `a` for which the program outputs itself.
+    pub fn find_quine(&self) -> Option<u64> {
+        self.search(&self.program, self.program.len(), 0)
+    }
+
+    fn search(&self, target: &[u8], remaining: usize, prefix: u64) -> Option<u64> {
+        if remaining == 0 {
+            return Some(prefix);
+        }
+
+        (0..8).find_map(|bits| {
+            let a = (prefix << 3) | bits;
+            if a == 0 {
+                return None;
+            }
+
+            let mut computer = Computer::new(
+                Registers {
+                    a,
+                    ..self.registers
+                },
+                self.program.clone(),
+            )
+            .with_step_limit(self.step_limit);
+
+            let matches = computer
+                .run()
+                .is_ok_and(|output| output == &target[remaining - 1..]);
+
+            if matches {
+                self.search(target, remaining - 1, a)
+            } else {
+                None
+            }
+        })
+    }
+}
+
+/// Right shift that yields zero instead of overflowing, which matches `a / 2^shift`.
+fn shr(value: u64, shift: u64) -> u64 {
+    u32::try_from(shift)
+        .ok()
+        .and_then(|shift| value.checked_shr(shift))
+        .unwrap_or(0)
+}
+
+impl FromStr for Computer {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut registers = Registers::default();
+        let mut program = None;
+
+        for line in s.lines().filter(|line| !line.trim().is_empty()) {
+            let (key, value) = line
+                .split_once(':')
+                .ok_or_else(|| Error::Parser(format!("unexpected line \"{line}\"")))?;
+            let value = value.trim();
+
+            let register = match key {
+                "Register A" => &mut registers.a,
+                "Register B" => &mut registers.b,
+                "Register C" => &mut registers.c,
+                "Program" => {
+                    let values = value
+                        .split(',')
+                        .map(|v| match v.trim().parse::<u8>() {
+                            Ok(v) if v < 8 => Ok(v),
+                            _ => Err(Error::Parser(format!("\"{v}\" is not a 3-bit number"))),
+                        })
+                        .collect::<Result<Vec<u8>, Error>>()?;
+                    program = Some(values);
+                    continue;
+                }
+                _ => return Err(Error::Parser(format!("unknown key \"{key}\""))),
+            };
+
+            *register = value
+                .parse()
+                .map_err(|_| Error::Parser(format!("\"{value}\" is not a register value")))?;
+        }
+
+        let program = program.ok_or_else(|| Error::Parser("missing program".into()))?;
+        Ok(Computer::new(registers, program))
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Computer, Error, Instruction, Registers};
+
+    fn computer(a: u64, program: &str) -> Computer {
+        format!("Register A: {a}\nRegister B: 0\nRegister C: 0\n\nProgram: {program}")
+            .parse()
+            .unwrap()
+    }
+
+    #[test]
+    fn runs_example() {
+        let mut computer = computer(729, "0,1,5,4,3,0");
+        computer.run().unwrap();
+        assert_eq!(computer.output(), [4, 6, 3, 5, 6, 3, 5, 2, 1, 0]);
+        assert_eq!(computer.output_string(), "4,6,3,5,6,3,5,2,1,0");
+    }
+
+    #[test]
+    fn runs_small_programs() {
+        let mut computer = Computer::new(Registers { a: 0, b: 0, c: 9 }, vec![2, 6]);
+        computer.run().unwrap();
+        assert_eq!(computer.registers.b, 1);
+
+        let mut computer = Computer::new(Registers { a: 0, b: 29, c: 0 }, vec![1, 7]);
+        computer.run().unwrap();
+        assert_eq!(computer.registers.b, 26);
+    }
+
+    #[test]
+    fn stops_runaway_programs() {
+        let mut computer = computer(1, "3,0").with_step_limit(100);
+        assert_eq!(computer.run(), Err(Error::StepLimit(100)));
+    }
+
+    #[test]
+    fn rejects_reserved_operand() {
+        let mut computer = computer(1, "0,7");
+        assert_eq!(computer.run(), Err(Error::ReservedOperand { ip: 0 }));
+        assert!("Program: 0,8".parse::<Computer>().is_err());
+    }
+
+    #[test]
+    fn disassembles_program() {
+        assert_eq!(
+            Instruction::decode(0, 3),
+            Some(Instruction::Adv(super::Combo::Literal(3)))
+        );
+        assert_eq!(
+            computer(2024, "0,3,5,4,3,0").disassemble(),
+            ["  0: a = a >> 3", "  2: out a % 8", "  4: if a != 0 goto 0"].join("\n")
+        );
+    }
+
+    #[test]
+    fn traces_registers() {
+        let mut lines = vec![];
+        computer(8, "0,3,5,4,3,0")
+            .run_traced(|trace| lines.push(trace.to_string()))
+            .unwrap();
+        assert_eq!(lines.len(), 6);
+        assert_eq!(lines[0], "  0: a = a >> 3           a=8 b=0 c=0");
+        assert_eq!(lines[3], "  0: a = a >> 3           a=1 b=0 c=0");
+    }
+
+    #[test]
+    fn finds_quine() {
+        let computer = computer(2024, "0,3,5,4,3,0");
+        assert_eq!(computer.find_quine(), Some(117_440));
+        assert_eq!(computer.find_register_a(&[3, 0]), Some(24));
+    }
+}
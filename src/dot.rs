@@ -0,0 +1,258 @@
+/// Module for writing graphs in the Graphviz DOT language.
+/// Render the output with e.g. `dot -Tsvg out.dot -o out.svg`.
+use std::fmt::{Display, Formatter, Result};
+
+static INDENT: &str = "    ";
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Attributes(Vec<(String, String)>);
+
+impl Attributes {
+    fn set(&mut self, key: &str, value: &str) {
+        match self.0.iter_mut().find(|(k, _)| k == key) {
+            Some((_, v)) => *v = value.to_string(),
+            None => self.0.push((key.to_string(), value.to_string())),
+        }
+    }
+
+    fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+/// Renders attributes as an attribute list, e.g. ` [shape=box, label="a b"]`.
+impl Display for Attributes {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        if self.0.is_empty() {
+            return Ok(());
+        }
+
+        let list: Vec<String> = self
+            .0
+            .iter()
+            .map(|(key, value)| format!("{}={}", quote(key), quote(value)))
+            .collect();
+        write!(f, " [{}]", list.join(", "))
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Node {
+    pub id: String,
+    pub attributes: Attributes,
+}
+
+impl Node {
+    pub fn attr(&mut self, key: &str, value: impl Display) -> &mut Self {
+        self.attributes.set(key, &value.to_string());
+        self
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Edge {
+    pub from: String,
+    pub to: String,
+    pub attributes: Attributes,
+}
+
+impl Edge {
+    pub fn attr(&mut self, key: &str, value: impl Display) -> &mut Self {
+        self.attributes.set(key, &value.to_string());
+        self
+    }
+}
+
+/// A graph, or a subgraph nested in one. Nodes and edges keep their insertion order.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Graph {
+    id: String,
+    directed: bool,
+    attributes: Attributes,
+    node_defaults: Attributes,
+    nodes: Vec<Node>,
+    edges: Vec<Edge>,
+    subgraphs: Vec<Graph>,
+}
+
+impl Graph {
+    fn new(id: &str, directed: bool) -> Self {
+        Graph {
+            id: id.to_string(),
+            directed,
+            attributes: Attributes::default(),
+            node_defaults: Attributes::default(),
+            nodes: vec![],
+            edges: vec![],
+            subgraphs: vec![],
+        }
+    }
+
+    /// A graph with directed edges.
+    pub fn digraph(id: &str) -> Self {
+        Graph::new(id, true)
+    }
+
+    /// A graph with undirected edges.
+    pub fn undirected(id: &str) -> Self {
+        Graph::new(id, false)
+    }
+
+    /// Sets a graph attribute, e.g. `rankdir`.
+    pub fn attr(&mut self, key: &str, value: impl Display) -> &mut Self {
+        self.attributes.set(key, &value.to_string());
+        self
+    }
+
+    /// Sets an attribute for all nodes of this graph.
+    pub fn node_attr(&mut self, key: &str, value: impl Display) -> &mut Self {
+        self.node_defaults.set(key, &value.to_string());
+        self
+    }
+
+    /// Adds a node, or returns the existing one with the same id.
+    pub fn node(&mut self, id: impl Display) -> &mut Node {
+        let id = id.to_string();
+
+        match self.nodes.iter().position(|node| node.id == id) {
+            Some(index) => &mut self.nodes[index],
+            None => {
+                self.nodes.push(Node {
+                    id,
+                    attributes: Attributes::default(),
+                });
+                self.nodes.last_mut().unwrap()
+            }
+        }
+    }
+
+    pub fn edge(&mut self, from: impl Display, to: impl Display) -> &mut Edge {
+        self.edges.push(Edge {
+            from: from.to_string(),
+            to: to.to_string(),
+            attributes: Attributes::default(),
+        });
+        self.edges.last_mut().unwrap()
+    }
+
+    /// Adds a subgraph, which groups nodes without drawing a border.
+    pub fn subgraph(&mut self, id: &str) -> &mut Graph {
+        self.subgraphs.push(Graph::new(id, self.directed));
+        self.subgraphs.last_mut().unwrap()
+    }
+
+    /// Adds a cluster, a subgraph that is drawn inside a border.
+    pub fn cluster(&mut self, id: &str) -> &mut Graph {
+        self.subgraph(&format!("cluster_{id}"))
+    }
+
+    fn write(&self, f: &mut Formatter<'_>, keyword: &str, depth: usize) -> Result {
+        let indent = INDENT.repeat(depth + 1);
+        let edge_op = if self.directed { "->" } else { "--" };
+
+        writeln!(f, "{keyword} {} {{", quote(&self.id))?;
+
+        for (key, value) in &self.attributes.0 {
+            writeln!(f, "{indent}{}={};", quote(key), quote(value))?;
+        }
+
+        if !self.node_defaults.is_empty() {
+            writeln!(f, "{indent}node{};", self.node_defaults)?;
+        }
+
+        for subgraph in &self.subgraphs {
+            write!(f, "{indent}")?;
+            subgraph.write(f, "subgraph", depth + 1)?;
+        }
+
+        for node in &self.nodes {
+            writeln!(f, "{indent}{}{};", quote(&node.id), node.attributes)?;
+        }
+
+        for edge in &self.edges {
+            writeln!(
+                f,
+                "{indent}{} {edge_op} {}{};",
+                quote(&edge.from),
+                quote(&edge.to),
+                edge.attributes
+            )?;
+        }
+
+        writeln!(f, "{}}}", INDENT.repeat(depth))
+    }
+}
+
+impl Display for Graph {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        let keyword = if self.directed { "digraph" } else { "graph" };
+        self.write(f, keyword, 0)
+    }
+}
+
+/// Quotes an id unless it is a plain identifier or number.
+fn quote(id: &str) -> String {
+    let is_identifier = id
+        .chars()
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && id.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+    let is_number = !id.is_empty() && id.chars().all(|c| c.is_ascii_digit());
+
+    if is_identifier || is_number {
+        id.to_string()
+    } else {
+        format!("\"{}\"", id.replace('\\', "\\\\").replace('"', "\\\""))
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::Graph;
+
+    #[test]
+    fn renders_digraph() {
+        let mut graph = Graph::digraph("rules");
+        graph.attr("rankdir", "LR").node_attr("shape", "box");
+        graph.node("a").attr("label", "page \"a\"");
+        graph.edge("a", 47).attr("color", "red");
+
+        assert_eq!(
+            graph.to_string(),
+            [
+                "digraph rules {",
+                "    rankdir=LR;",
+                "    node [shape=box];",
+                "    a [label=\"page \\\"a\\\"\"];",
+                "    a -> 47 [color=red];",
+                "}",
+                "",
+            ]
+            .join("\n")
+        );
+    }
+
+    #[test]
+    fn renders_clusters() {
+        let mut graph = Graph::undirected("lan");
+        graph.cluster("party").attr("label", "LAN party").node("co");
+        graph.edge("co", "de");
+        graph.node("co").attr("color", "blue");
+
+        assert_eq!(
+            graph.to_string(),
+            [
+                "graph lan {",
+                "    subgraph cluster_party {",
+                "        label=\"LAN party\";",
+                "        co;",
+                "    }",
+                "    co [color=blue];",
+                "    co -- de;",
+                "}",
+                "",
+            ]
+            .join("\n")
+        );
+    }
+}
@@ -0,0 +1,356 @@
+/// Module for calibration equations of day 7.
+/// Operators are evaluated left to right and searched in reverse, from the target back to the
+/// first number, so that operators can prune branches which cannot produce the target.
+use std::{fmt::Display, str::FromStr};
+
+/// A binary operator that can be placed between two numbers of an equation.
+pub trait Operator {
+    fn symbol(&self) -> &str;
+
+    /// Evaluates `left op right`, or `None` if the result is not a `u64`.
+    fn apply(&self, left: u64, right: u64) -> Option<u64>;
+
+    /// Returns the `left` for which `left op right == result`, or `None` if there is none.
+    /// Must agree with `apply`, as the solver only searches in this direction.
+    fn invert(&self, result: u64, right: u64) -> Option<u64>;
+
+    /// Whether `left op right == result` holds for every `left`, e.g. `left * 0 == 0`.
+    /// `invert` cannot name a single `left` then, so the solver evaluates the numbers before
+    /// `right` forward instead.
+    fn absorbs(&self, _result: u64, _right: u64) -> bool {
+        false
+    }
+}
+
+pub struct Add;
+
+impl Operator for Add {
+    fn symbol(&self) -> &str {
+        "+"
+    }
+
+    fn apply(&self, left: u64, right: u64) -> Option<u64> {
+        left.checked_add(right)
+    }
+
+    fn invert(&self, result: u64, right: u64) -> Option<u64> {
+        result.checked_sub(right)
+    }
+}
+
+/// Subtraction which does not go below zero.
+pub struct Subtract;
+
+impl Operator for Subtract {
+    fn symbol(&self) -> &str {
+        "-"
+    }
+
+    fn apply(&self, left: u64, right: u64) -> Option<u64> {
+        left.checked_sub(right)
+    }
+
+    fn invert(&self, result: u64, right: u64) -> Option<u64> {
+        result.checked_add(right)
+    }
+}
+
+/// Multiplication. A `right` of zero absorbs any `left`.
+pub struct Multiply;
+
+impl Operator for Multiply {
+    fn symbol(&self) -> &str {
+        "*"
+    }
+
+    fn apply(&self, left: u64, right: u64) -> Option<u64> {
+        left.checked_mul(right)
+    }
+
+    fn invert(&self, result: u64, right: u64) -> Option<u64> {
+        (right != 0 && result.is_multiple_of(right)).then(|| result / right)
+    }
+
+    fn absorbs(&self, result: u64, right: u64) -> bool {
+        right == 0 && result == 0
+    }
+}
+
+/// Appends the digits of `right` to `left`, written in `base`.
+pub struct Concat {
+    base: u64,
+}
+
+impl Concat {
+    pub const DECIMAL: Concat = Concat { base: 10 };
+
+    /// Concatenation in `base`, which has to be at least 2 for numbers to have digits.
+    pub fn new(base: u64) -> Result<Self, Error> {
+        if base < 2 {
+            return Err(Error(format!("invalid concatenation base {base}")));
+        }
+        Ok(Concat { base })
+    }
+
+    pub fn base(&self) -> u64 {
+        self.base
+    }
+
+    /// The power of `base` with as many digits as `value`.
+    fn offset(&self, value: u64) -> Option<u64> {
+        let digits = if value == 0 {
+            1
+        } else {
+            value.ilog(self.base) + 1
+        };
+        self.base.checked_pow(digits)
+    }
+}
+
+impl Operator for Concat {
+    fn symbol(&self) -> &str {
+        "||"
+    }
+
+    fn apply(&self, left: u64, right: u64) -> Option<u64> {
+        left.checked_mul(self.offset(right)?)?.checked_add(right)
+    }
+
+    fn invert(&self, result: u64, right: u64) -> Option<u64> {
+        let offset = self.offset(right)?;
+        (result % offset == right).then(|| result / offset)
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct Error(String);
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// Numbers with the operators placed between them, e.g. `11 + 6 * 16 + 20`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Expression {
+    pub numbers: Vec<u64>,
+    pub operators: Vec<String>,
+}
+
+impl Display for Expression {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.numbers[0])?;
+        for (operator, number) in self.operators.iter().zip(&self.numbers[1..]) {
+            write!(f, " {operator} {number}")?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Solutions {
+    /// Number of operator assignments that produce the target.
+    pub count: u64,
+    pub example: Option<Expression>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Equation {
+    pub target: u64,
+    pub numbers: Vec<u64>,
+}
+
+impl Equation {
+    /// Whether any assignment of `operators` produces the target. Stops at the first one found.
+    pub fn is_solvable(&self, operators: &[&dyn Operator]) -> bool {
+        self.search(self.target, self.numbers.len() - 1, operators)
+    }
+
+    fn search(&self, value: u64, index: usize, operators: &[&dyn Operator]) -> bool {
+        if index == 0 {
+            return value == self.numbers[0];
+        }
+
+        operators.iter().any(|operator| {
+            if operator.absorbs(value, self.numbers[index]) {
+                let mut assignment = vec![0; index - 1];
+                return self.evaluate(self.numbers[0], 1, operators, &mut assignment, &mut |_| {
+                    true
+                });
+            }
+            operator
+                .invert(value, self.numbers[index])
+                .is_some_and(|left| self.search(left, index - 1, operators))
+        })
+    }
+
+    /// Calls `found` with each assignment of `operators` between the numbers up to
+    /// `assignment.len()` whose forward evaluation does not fail, starting from `value` at
+    /// `index`. Stops once `found` returns `true`, and returns whether it did.
+    fn evaluate(
+        &self,
+        value: u64,
+        index: usize,
+        operators: &[&dyn Operator],
+        assignment: &mut [usize],
+        found: &mut impl FnMut(&[usize]) -> bool,
+    ) -> bool {
+        if index > assignment.len() {
+            return found(assignment);
+        }
+
+        for (i, operator) in operators.iter().enumerate() {
+            if let Some(next) = operator.apply(value, self.numbers[index]) {
+                assignment[index - 1] = i;
+                if self.evaluate(next, index + 1, operators, assignment, found) {
+                    return true;
+                }
+            }
+        }
+        false
+    }
+
+    /// Counts all assignments of `operators` that produce the target, and keeps the first one.
+    pub fn solve(&self, operators: &[&dyn Operator]) -> Solutions {
+        let mut solutions = Solutions::default();
+        let mut assignment = vec![0; self.numbers.len() - 1];
+        self.count(
+            self.target,
+            self.numbers.len() - 1,
+            operators,
+            &mut assignment,
+            &mut solutions,
+        );
+        solutions
+    }
+
+    fn count(
+        &self,
+        value: u64,
+        index: usize,
+        operators: &[&dyn Operator],
+        assignment: &mut [usize],
+        solutions: &mut Solutions,
+    ) {
+        if index == 0 {
+            if value == self.numbers[0] {
+                self.record(assignment, operators, solutions);
+            }
+            return;
+        }
+
+        for (i, operator) in operators.iter().enumerate() {
+            if operator.absorbs(value, self.numbers[index]) {
+                assignment[index - 1] = i;
+                let (prefix, suffix) = assignment.split_at_mut(index - 1);
+                self.evaluate(self.numbers[0], 1, operators, prefix, &mut |prefix| {
+                    let full: Vec<usize> = prefix.iter().chain(suffix.iter()).copied().collect();
+                    self.record(&full, operators, solutions);
+                    false
+                });
+            } else if let Some(left) = operator.invert(value, self.numbers[index]) {
+                assignment[index - 1] = i;
+                self.count(left, index - 1, operators, assignment, solutions);
+            }
+        }
+    }
+
+    fn record(&self, assignment: &[usize], operators: &[&dyn Operator], solutions: &mut Solutions) {
+        solutions.count += 1;
+        solutions.example.get_or_insert_with(|| Expression {
+            numbers: self.numbers.clone(),
+            operators: assignment
+                .iter()
+                .map(|&i| operators[i].symbol().to_string())
+                .collect(),
+        });
+    }
+}
+
+/// Parses equations like `190: 10 19`.
+impl FromStr for Equation {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (target, numbers) = s
+            .split_once(':')
+            .ok_or_else(|| Error(format!("missing `:` in \"{s}\"")))?;
+        let target = target
+            .trim()
+            .parse()
+            .map_err(|_| Error(format!("invalid target \"{target}\"")))?;
+        let numbers = numbers
+            .split_ascii_whitespace()
+            .map(|number| {
+                number
+                    .parse()
+                    .map_err(|_| Error(format!("invalid number \"{number}\"")))
+            })
+            .collect::<Result<Vec<u64>, Error>>()?;
+
+        if numbers.is_empty() {
+            return Err(Error(format!("no numbers in \"{s}\"")));
+        }
+
+        Ok(Equation { target, numbers })
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Add, Concat, Equation, Multiply, Operator, Subtract};
+
+    fn equation(s: &str) -> Equation {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn solves_with_add_and_multiply() {
+        let operators: [&dyn Operator; 2] = [&Add, &Multiply];
+        assert!(equation("3267: 81 40 27").is_solvable(&operators));
+        assert!(!equation("156: 15 6").is_solvable(&operators));
+
+        let solutions = equation("3267: 81 40 27").solve(&operators);
+        assert_eq!(solutions.count, 2);
+        assert_eq!(solutions.example.unwrap().to_string(), "81 * 40 + 27");
+    }
+
+    #[test]
+    fn solves_with_concat() {
+        let operators: [&dyn Operator; 3] = [&Add, &Multiply, &Concat::DECIMAL];
+        let solutions = equation("7290: 6 8 6 15").solve(&operators);
+        assert_eq!(solutions.count, 1);
+        assert_eq!(solutions.example.unwrap().to_string(), "6 * 8 || 6 * 15");
+    }
+
+    #[test]
+    fn supports_other_operators() {
+        let binary = Concat::new(2).unwrap();
+        assert_eq!(binary.apply(0b101, 0b11), Some(0b10111));
+        assert_eq!(binary.invert(0b10111, 0b11), Some(0b101));
+        assert!(Concat::new(1).is_err() && Concat::new(0).is_err());
+        assert!(equation("5: 3 2 5").is_solvable(&[&Subtract, &Multiply]));
+        assert!(!equation("5: 2 3").is_solvable(&[&Subtract]));
+    }
+
+    #[test]
+    fn solves_multiplication_by_zero() {
+        let operators: [&dyn Operator; 2] = [&Add, &Multiply];
+        assert!(equation("0: 5 0").is_solvable(&operators));
+        assert_eq!(equation("0: 5 0").solve(&operators).count, 1);
+
+        // `5 ? 3 * 0` works for both operators between 5 and 3, `5 * 3 + 0` does not.
+        let solutions = equation("0: 5 3 0").solve(&operators);
+        assert_eq!(solutions.count, 2);
+        assert_eq!(solutions.example.unwrap().to_string(), "5 + 3 * 0");
+        // `0 * 4 + 0` is found by inverting, the other two through the absorbing `* 0`.
+        assert_eq!(equation("0: 0 4 0").solve(&operators).count, 3);
+    }
+
+    #[test]
+    fn rejects_invalid_equations() {
+        assert!("190 10 19".parse::<Equation>().is_err());
+        assert!("190:".parse::<Equation>().is_err());
+    }
+}
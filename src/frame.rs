@@ -0,0 +1,218 @@
+/// Module for black and white frames, e.g. robot positions, that can be saved as images.
+use std::{fmt::Display, str::FromStr};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FrameFormat {
+    /// Portable bitmap, readable by most image viewers.
+    Pbm,
+    Png,
+    /// `#` for set and `.` for unset pixels, one line per row.
+    Ascii,
+}
+
+impl FrameFormat {
+    pub fn extension(self) -> &'static str {
+        match self {
+            FrameFormat::Pbm => "pbm",
+            FrameFormat::Png => "png",
+            FrameFormat::Ascii => "txt",
+        }
+    }
+}
+
+impl FromStr for FrameFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "pbm" => Ok(FrameFormat::Pbm),
+            "png" => Ok(FrameFormat::Png),
+            "ascii" | "txt" => Ok(FrameFormat::Ascii),
+            _ => Err(format!(
+                "unknown frame format \"{s}\", expecting `pbm`, `png` or `ascii`"
+            )),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Frame {
+    /// File name of the frame, without extension.
+    pub name: String,
+    pub width: usize,
+    pub height: usize,
+    pixels: Vec<bool>,
+}
+
+impl Frame {
+    pub fn new(name: impl Display, width: usize, height: usize) -> Self {
+        Frame {
+            name: name.to_string(),
+            width,
+            height,
+            pixels: vec![false; width * height],
+        }
+    }
+
+    /// Sets the pixel at `x`, `y`. Pixels outside of the frame are ignored.
+    pub fn set(&mut self, x: usize, y: usize) {
+        if x < self.width && y < self.height {
+            self.pixels[y * self.width + x] = true;
+        }
+    }
+
+    pub fn get(&self, x: usize, y: usize) -> bool {
+        x < self.width && y < self.height && self.pixels[y * self.width + x]
+    }
+
+    fn rows(&self) -> impl Iterator<Item = &[bool]> {
+        self.pixels.chunks(self.width.max(1))
+    }
+
+    pub fn encode(&self, format: FrameFormat) -> Vec<u8> {
+        match format {
+            FrameFormat::Pbm => self.to_pbm(),
+            FrameFormat::Png => self.to_png(),
+            FrameFormat::Ascii => self.to_string().into_bytes(),
+        }
+    }
+
+    /// Plain PBM, where `1` is a black pixel.
+    fn to_pbm(&self) -> Vec<u8> {
+        let mut lines = vec!["P1".to_string(), format!("{} {}", self.width, self.height)];
+        lines.extend(self.rows().map(|row| {
+            row.iter()
+                .map(|&set| if set { "1" } else { "0" })
+                .collect::<Vec<_>>()
+                .join(" ")
+        }));
+        (lines.join("\n") + "\n").into_bytes()
+    }
+
+    /// Grayscale PNG with set pixels in black. The image data is stored without compression.
+    fn to_png(&self) -> Vec<u8> {
+        let mut raw = Vec::with_capacity((self.width + 1) * self.height);
+        for row in self.rows() {
+            // filter type `None` for each scanline.
+            raw.push(0);
+            raw.extend(row.iter().map(|&set| if set { 0 } else { 255 }));
+        }
+
+        let mut header = vec![];
+        header.extend(u32::try_from(self.width).unwrap().to_be_bytes());
+        header.extend(u32::try_from(self.height).unwrap().to_be_bytes());
+        // bit depth 8, grayscale, deflate, adaptive filtering, no interlace.
+        header.extend([8, 0, 0, 0, 0]);
+
+        let mut png = b"\x89PNG\r\n\x1a\n".to_vec();
+        write_chunk(&mut png, b"IHDR", &header);
+        write_chunk(&mut png, b"IDAT", &zlib_stored(&raw));
+        write_chunk(&mut png, b"IEND", &[]);
+        png
+    }
+}
+
+impl Display for Frame {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for row in self.rows() {
+            let line: String = row.iter().map(|&set| if set { '#' } else { '.' }).collect();
+            writeln!(f, "{line}")?;
+        }
+        Ok(())
+    }
+}
+
+fn write_chunk(png: &mut Vec<u8>, kind: &[u8; 4], data: &[u8]) {
+    png.extend(u32::try_from(data.len()).unwrap().to_be_bytes());
+    let start = png.len();
+    png.extend(kind);
+    png.extend(data);
+    let crc = crc32(&png[start..]);
+    png.extend(crc.to_be_bytes());
+}
+
+/// Wraps `data` in a zlib stream of uncompressed deflate blocks.
+fn zlib_stored(data: &[u8]) -> Vec<u8> {
+    let mut out = vec![0x78, 0x01];
+    let mut blocks = data.chunks(usize::from(u16::MAX)).peekable();
+
+    if blocks.peek().is_none() {
+        out.extend([1, 0, 0, 0xff, 0xff]);
+    }
+
+    while let Some(block) = blocks.next() {
+        let is_last = blocks.peek().is_none();
+        let len = u16::try_from(block.len()).unwrap();
+        out.push(u8::from(is_last));
+        out.extend(len.to_le_bytes());
+        out.extend((!len).to_le_bytes());
+        out.extend(block);
+    }
+
+    out.extend(adler32(data).to_be_bytes());
+    out
+}
+
+fn crc32(data: &[u8]) -> u32 {
+    let mut crc = u32::MAX;
+    for &byte in data {
+        crc ^= u32::from(byte);
+        for _ in 0..8 {
+            let mask = (crc & 1).wrapping_neg();
+            crc = (crc >> 1) ^ (0xedb8_8320 & mask);
+        }
+    }
+    !crc
+}
+
+fn adler32(data: &[u8]) -> u32 {
+    let (mut a, mut b) = (1u32, 0u32);
+    for &byte in data {
+        a = (a + u32::from(byte)) % 65521;
+        b = (b + a) % 65521;
+    }
+    (b << 16) | a
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{adler32, crc32, Frame, FrameFormat};
+
+    fn frame() -> Frame {
+        let mut frame = Frame::new("test", 3, 2);
+        frame.set(0, 0);
+        frame.set(2, 1);
+        frame.set(5, 5);
+        frame
+    }
+
+    #[test]
+    fn renders_ascii_and_pbm() {
+        let frame = frame();
+        assert_eq!(frame.to_string(), "#..\n..#\n");
+        assert_eq!(
+            String::from_utf8(frame.encode(FrameFormat::Pbm)).unwrap(),
+            "P1\n3 2\n1 0 0\n0 0 1\n"
+        );
+    }
+
+    #[test]
+    fn encodes_png() {
+        let png = frame().encode(FrameFormat::Png);
+        assert_eq!(&png[..8], b"\x89PNG\r\n\x1a\n");
+        assert_eq!(&png[12..16], b"IHDR");
+        assert_eq!(&png[png.len() - 8..png.len() - 4], b"IEND");
+    }
+
+    #[test]
+    fn computes_checksums() {
+        assert_eq!(crc32(b"IEND"), 0xae42_6082);
+        assert_eq!(adler32(b"Wikipedia"), 0x11e6_0398);
+    }
+
+    #[test]
+    fn parses_formats() {
+        assert_eq!("png".parse(), Ok(FrameFormat::Png));
+        assert!("gif".parse::<FrameFormat>().is_err());
+    }
+}
@@ -0,0 +1,343 @@
+/// Module for undirected graphs with named nodes, e.g. the LAN of day 23.
+/// Names are interned to dense ids so that neighbourhoods can be stored as bitsets.
+use std::collections::HashMap;
+
+/// A set of small non-negative integers, stored as one bit each.
+#[derive(Clone, Debug, Default)]
+pub struct BitSet {
+    words: Vec<u64>,
+}
+
+impl BitSet {
+    pub fn new() -> Self {
+        BitSet::default()
+    }
+
+    pub fn insert(&mut self, value: usize) {
+        let (word, bit) = (value / 64, value % 64);
+        if word >= self.words.len() {
+            self.words.resize(word + 1, 0);
+        }
+        self.words[word] |= 1 << bit;
+    }
+
+    pub fn remove(&mut self, value: usize) {
+        if let Some(word) = self.words.get_mut(value / 64) {
+            *word &= !(1 << (value % 64));
+        }
+    }
+
+    pub fn contains(&self, value: usize) -> bool {
+        self.words
+            .get(value / 64)
+            .is_some_and(|word| word & (1 << (value % 64)) != 0)
+    }
+
+    pub fn len(&self) -> usize {
+        self.words
+            .iter()
+            .map(|word| word.count_ones() as usize)
+            .sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.words.iter().all(|&word| word == 0)
+    }
+
+    pub fn intersection(&self, other: &BitSet) -> BitSet {
+        BitSet {
+            words: self
+                .words
+                .iter()
+                .zip(&other.words)
+                .map(|(a, b)| a & b)
+                .collect(),
+        }
+    }
+
+    pub fn difference(&self, other: &BitSet) -> BitSet {
+        BitSet {
+            words: self
+                .words
+                .iter()
+                .enumerate()
+                .map(|(i, a)| a & !other.words.get(i).unwrap_or(&0))
+                .collect(),
+        }
+    }
+
+    pub fn intersection_len(&self, other: &BitSet) -> usize {
+        self.words
+            .iter()
+            .zip(&other.words)
+            .map(|(a, b)| (a & b).count_ones() as usize)
+            .sum()
+    }
+
+    /// Values in ascending order.
+    pub fn iter(&self) -> impl Iterator<Item = usize> + '_ {
+        self.words.iter().enumerate().flat_map(|(i, &word)| {
+            let mut word = word;
+            std::iter::from_fn(move || {
+                if word == 0 {
+                    return None;
+                }
+                let bit = word.trailing_zeros() as usize;
+                word &= word - 1;
+                Some(i * 64 + bit)
+            })
+        })
+    }
+}
+
+/// Sets are equal if they contain the same values, regardless of trailing zero words.
+impl PartialEq for BitSet {
+    fn eq(&self, other: &Self) -> bool {
+        let (shorter, longer) = if self.words.len() <= other.words.len() {
+            (&self.words, &other.words)
+        } else {
+            (&other.words, &self.words)
+        };
+        shorter == &longer[..shorter.len()] && longer[shorter.len()..].iter().all(|&w| w == 0)
+    }
+}
+
+impl Eq for BitSet {}
+
+impl FromIterator<usize> for BitSet {
+    fn from_iter<T: IntoIterator<Item = usize>>(iter: T) -> Self {
+        let mut set = BitSet::new();
+        for value in iter {
+            set.insert(value);
+        }
+        set
+    }
+}
+
+/// An undirected graph without self-loops. Node ids are assigned in order of first appearance.
+#[derive(Clone, Debug, Default)]
+pub struct Graph {
+    names: Vec<String>,
+    ids: HashMap<String, usize>,
+    adjacency: Vec<BitSet>,
+}
+
+impl Graph {
+    pub fn new() -> Self {
+        Graph::default()
+    }
+
+    pub fn from_edges<'a>(edges: impl IntoIterator<Item = (&'a str, &'a str)>) -> Self {
+        let mut graph = Graph::new();
+        for (a, b) in edges {
+            graph.add_edge(a, b);
+        }
+        graph
+    }
+
+    /// Returns the id of `name`, adding it as a node if it is new.
+    pub fn intern(&mut self, name: &str) -> usize {
+        if let Some(&id) = self.ids.get(name) {
+            return id;
+        }
+
+        let id = self.names.len();
+        self.names.push(name.to_string());
+        self.ids.insert(name.to_string(), id);
+        self.adjacency.push(BitSet::new());
+        id
+    }
+
+    pub fn add_edge(&mut self, a: &str, b: &str) {
+        let (a, b) = (self.intern(a), self.intern(b));
+        if a != b {
+            self.adjacency[a].insert(b);
+            self.adjacency[b].insert(a);
+        }
+    }
+
+    pub fn id(&self, name: &str) -> Option<usize> {
+        self.ids.get(name).copied()
+    }
+
+    pub fn name(&self, id: usize) -> &str {
+        &self.names[id]
+    }
+
+    pub fn len(&self) -> usize {
+        self.names.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.names.is_empty()
+    }
+
+    pub fn neighbours(&self, id: usize) -> &BitSet {
+        &self.adjacency[id]
+    }
+
+    pub fn is_edge(&self, a: usize, b: usize) -> bool {
+        self.adjacency[a].contains(b)
+    }
+
+    /// Edges as pairs of ids, each listed once with the lower id first.
+    pub fn edges(&self) -> impl Iterator<Item = (usize, usize)> + '_ {
+        self.adjacency
+            .iter()
+            .enumerate()
+            .flat_map(|(a, neighbours)| {
+                neighbours
+                    .iter()
+                    .filter(move |&b| b > a)
+                    .map(move |b| (a, b))
+            })
+    }
+
+    /// Triangles with at least one node for which `predicate` holds, as ascending ids.
+    pub fn triangles(&self, predicate: impl Fn(usize) -> bool) -> Vec<[usize; 3]> {
+        let mut triangles = vec![];
+        for (a, b) in self.edges() {
+            for c in self.adjacency[a].intersection(&self.adjacency[b]).iter() {
+                if c > b && (predicate(a) || predicate(b) || predicate(c)) {
+                    triangles.push([a, b, c]);
+                }
+            }
+        }
+        triangles
+    }
+
+    /// All maximal cliques, found with Bron–Kerbosch with pivoting.
+    pub fn maximal_cliques(&self) -> Vec<BitSet> {
+        let mut cliques = vec![];
+        let candidates = (0..self.len()).collect();
+        self.bron_kerbosch(BitSet::new(), candidates, BitSet::new(), &mut |clique| {
+            cliques.push(clique.clone())
+        });
+        cliques
+    }
+
+    /// A largest clique. Ties are broken by the first one found.
+    pub fn maximum_clique(&self) -> BitSet {
+        let mut best = BitSet::new();
+        let candidates = (0..self.len()).collect();
+        self.bron_kerbosch(BitSet::new(), candidates, BitSet::new(), &mut |clique| {
+            if clique.len() > best.len() {
+                best = clique.clone();
+            }
+        });
+        best
+    }
+
+    /// Reports every maximal clique that extends `clique` with nodes of `candidates` but none
+    /// of `excluded`. Only candidates which are not neighbours of the pivot are branched on,
+    /// as any clique containing a neighbour of the pivot is found through the pivot or
+    /// one of its other non-neighbours.
+    fn bron_kerbosch(
+        &self,
+        clique: BitSet,
+        mut candidates: BitSet,
+        mut excluded: BitSet,
+        report: &mut impl FnMut(&BitSet),
+    ) {
+        if candidates.is_empty() {
+            if excluded.is_empty() {
+                report(&clique);
+            }
+            return;
+        }
+
+        let pivot = candidates
+            .iter()
+            .chain(excluded.iter())
+            .max_by_key(|&node| candidates.intersection_len(&self.adjacency[node]))
+            .unwrap();
+
+        let branches = candidates.difference(&self.adjacency[pivot]);
+        for node in branches.iter() {
+            let neighbours = &self.adjacency[node];
+            let mut next_clique = clique.clone();
+            next_clique.insert(node);
+            self.bron_kerbosch(
+                next_clique,
+                candidates.intersection(neighbours),
+                excluded.intersection(neighbours),
+                report,
+            );
+            candidates.remove(node);
+            excluded.insert(node);
+        }
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{BitSet, Graph};
+
+    fn graph() -> Graph {
+        // a square with one diagonal, and a separate edge.
+        Graph::from_edges([
+            ("a", "b"),
+            ("b", "c"),
+            ("c", "d"),
+            ("d", "a"),
+            ("a", "c"),
+            ("x", "y"),
+        ])
+    }
+
+    #[test]
+    fn stores_bitsets() {
+        let mut set: BitSet = [3, 64, 130].into_iter().collect();
+        assert!(set.contains(64) && !set.contains(65) && !set.contains(1000));
+        set.remove(64);
+        assert_eq!(set.iter().collect::<Vec<_>>(), vec![3, 130]);
+        assert_eq!(set.len(), 2);
+
+        let other: BitSet = [3, 4].into_iter().collect();
+        let expected: BitSet = [3].into_iter().collect();
+        assert_eq!(set.intersection(&other), expected);
+        assert_eq!(other.intersection(&set), expected);
+        assert_eq!(set.difference(&[130].into_iter().collect()), expected);
+        assert_ne!(set, expected);
+
+        let mut emptied: BitSet = [200].into_iter().collect();
+        emptied.remove(200);
+        assert_eq!(emptied, BitSet::new());
+
+        assert_eq!(set.intersection(&other).iter().collect::<Vec<_>>(), vec![3]);
+        assert_eq!(set.difference(&other).iter().collect::<Vec<_>>(), vec![130]);
+    }
+
+    #[test]
+    fn interns_names() {
+        let graph = graph();
+        assert_eq!(graph.len(), 6);
+        assert_eq!(graph.id("c"), Some(2));
+        assert_eq!(graph.name(4), "x");
+        assert!(graph.is_edge(0, 2) && !graph.is_edge(1, 3));
+        assert_eq!(graph.edges().count(), 6);
+    }
+
+    #[test]
+    fn enumerates_triangles() {
+        let graph = graph();
+        assert_eq!(graph.triangles(|_| true), vec![[0, 1, 2], [0, 2, 3]]);
+        let d = graph.id("d").unwrap();
+        assert_eq!(graph.triangles(|node| node == d), vec![[0, 2, 3]]);
+    }
+
+    #[test]
+    fn finds_cliques() {
+        let graph = graph();
+        let names =
+            |clique: &BitSet| -> Vec<&str> { clique.iter().map(|id| graph.name(id)).collect() };
+
+        let mut cliques: Vec<Vec<&str>> = graph.maximal_cliques().iter().map(names).collect();
+        cliques.sort();
+        assert_eq!(
+            cliques,
+            vec![vec!["a", "b", "c"], vec!["a", "c", "d"], vec!["x", "y"]]
+        );
+        assert_eq!(graph.maximum_clique().len(), 3);
+    }
+}
@@ -0,0 +1,421 @@
+/// Module for the guard patrolling the lab of day 6.
+/// The guard walks straight until the cell ahead is an obstacle and then turns right. Jump
+/// tables store where each walk ends, so a patrol takes one lookup per turn instead of one per
+/// cell.
+use std::{fmt::Display, str::FromStr, sync::OnceLock};
+
+#[derive(Debug, Eq, Hash, PartialEq, Clone, Copy)]
+pub enum Direction {
+    Up,
+    Right,
+    Down,
+    Left,
+}
+
+impl Direction {
+    const ALL: [Direction; 4] = [
+        Direction::Up,
+        Direction::Right,
+        Direction::Down,
+        Direction::Left,
+    ];
+
+    pub fn turn_right(self) -> Self {
+        Direction::ALL[(self as usize + 1) % 4]
+    }
+
+    /// Bit of the direction in a direction mask.
+    pub fn bit(self) -> u8 {
+        1 << self as u8
+    }
+
+    fn is_vertical(self) -> bool {
+        matches!(self, Direction::Up | Direction::Down)
+    }
+
+    fn symbol(self) -> char {
+        match self {
+            Direction::Up => '^',
+            Direction::Right => '>',
+            Direction::Down => 'v',
+            Direction::Left => '<',
+        }
+    }
+}
+
+/// Row and column of a cell.
+#[derive(Debug, Eq, Hash, PartialEq, Clone, Copy)]
+pub struct Position(pub usize, pub usize);
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct Error(String);
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// Cells visited by the guard, in order, with the directions the guard faced in each cell.
+#[derive(Clone, Debug)]
+pub struct Path {
+    /// Each cell the guard stood in and the direction it was about to move in. After a turn,
+    /// the same cell appears again with the new direction.
+    pub steps: Vec<(Position, Direction)>,
+    /// Whether the guard ended up walking in a loop instead of leaving the lab.
+    pub is_loop: bool,
+    width: usize,
+    directions: Vec<u8>,
+}
+
+impl Path {
+    /// Mask of all directions the guard faced in `position`, see [`Direction::bit`].
+    pub fn directions(&self, position: Position) -> u8 {
+        self.directions[position.0 * self.width + position.1]
+    }
+
+    /// Number of distinct cells visited.
+    pub fn visited(&self) -> usize {
+        self.directions.iter().filter(|&&mask| mask != 0).count()
+    }
+}
+
+/// Jump table entry of walks that leave the lab.
+const EXIT: u32 = u32::MAX;
+
+/// The lab, with obstacles stored as a dense grid.
+#[derive(Clone, Debug)]
+pub struct Lab {
+    width: usize,
+    height: usize,
+    obstacles: Vec<bool>,
+    start: (usize, Direction),
+    /// Cell in which a walk from each cell in each direction ends, or `EXIT` if the guard
+    /// leaves the lab. Built on first use, as walking cell by cell does not need them.
+    jumps: OnceLock<[Vec<u32>; 4]>,
+}
+
+impl Lab {
+    pub fn start(&self) -> (Position, Direction) {
+        (self.position(self.start.0), self.start.1)
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn is_obstacle(&self, position: Position) -> bool {
+        self.obstacles[self.cell(position)]
+    }
+
+    fn cell(&self, position: Position) -> usize {
+        position.0 * self.width + position.1
+    }
+
+    fn position(&self, cell: usize) -> Position {
+        Position(cell / self.width, cell % self.width)
+    }
+
+    /// The cell next to `cell` in `direction`, or `None` at the edge of the lab.
+    fn ahead(&self, cell: usize, direction: Direction) -> Option<usize> {
+        let Position(row, column) = self.position(cell);
+        match direction {
+            Direction::Up => (row > 0).then(|| cell - self.width),
+            Direction::Right => (column + 1 < self.width).then(|| cell + 1),
+            Direction::Down => (row + 1 < self.height).then(|| cell + self.width),
+            Direction::Left => (column > 0).then(|| cell - 1),
+        }
+    }
+
+    /// Number of steps from `from` to `to` in `direction`, if `to` lies ahead in that direction.
+    fn distance(&self, from: usize, to: usize, direction: Direction) -> Option<usize> {
+        let (Position(r1, c1), Position(r2, c2)) = (self.position(from), self.position(to));
+        match direction {
+            Direction::Up if c1 == c2 && r2 < r1 => Some(r1 - r2),
+            Direction::Down if c1 == c2 && r2 > r1 => Some(r2 - r1),
+            Direction::Left if r1 == r2 && c2 < c1 => Some(c1 - c2),
+            Direction::Right if r1 == r2 && c2 > c1 => Some(c2 - c1),
+            _ => None,
+        }
+    }
+
+    fn build_jumps(&self) -> [Vec<u32>; 4] {
+        let mut tables: [Vec<u32>; 4] = Default::default();
+        let (width, height) = (self.width, self.height);
+        for direction in Direction::ALL {
+            // each line of cells is scanned starting from the edge the guard walks towards,
+            // given as first cell, stride and number of cells.
+            let lines: Vec<(usize, isize, usize)> = match direction {
+                Direction::Up => (0..width).map(|c| (c, width as isize, height)).collect(),
+                Direction::Down => (0..width)
+                    .map(|c| ((height - 1) * width + c, -(width as isize), height))
+                    .collect(),
+                Direction::Left => (0..height).map(|r| (r * width, 1, width)).collect(),
+                Direction::Right => (0..height)
+                    .map(|r| (r * width + width - 1, -1, width))
+                    .collect(),
+            };
+
+            let mut jumps = vec![EXIT; self.obstacles.len()];
+            for (first, stride, len) in lines {
+                let mut end = EXIT;
+                let mut is_after_obstacle = false;
+                for i in 0..len {
+                    let cell = first.wrapping_add_signed(stride * i as isize);
+                    if self.obstacles[cell] {
+                        is_after_obstacle = true;
+                        continue;
+                    }
+                    if is_after_obstacle {
+                        end = cell as u32;
+                        is_after_obstacle = false;
+                    }
+                    jumps[cell] = end;
+                }
+            }
+            tables[direction as usize] = jumps;
+        }
+        tables
+    }
+
+    /// Where a walk from `cell` in `direction` ends, taking one `extra` obstacle into account.
+    fn jump(&self, cell: usize, direction: Direction, extra: Option<usize>) -> Option<usize> {
+        let end = self.jumps.get_or_init(|| self.build_jumps())[direction as usize][cell];
+        let end = (end != EXIT).then_some(end as usize);
+        let Some(extra) = extra else {
+            return end;
+        };
+
+        match self.distance(cell, extra, direction) {
+            Some(to_extra)
+                if end.is_none_or(|end| {
+                    self.distance(cell, end, direction).unwrap_or(0) >= to_extra
+                }) =>
+            {
+                // stop one cell before the extra obstacle.
+                let opposite = direction.turn_right().turn_right();
+                self.ahead(extra, opposite)
+            }
+            _ => end,
+        }
+    }
+
+    /// Whether the guard walks in a loop when starting in `from` with one `extra` obstacle.
+    /// A loop is found once the guard turns into the same direction in the same cell twice.
+    pub fn has_loop(&self, from: (Position, Direction), extra: Option<Position>) -> bool {
+        let extra = extra.map(|position| self.cell(position));
+        let (mut cell, mut direction) = (self.cell(from.0), from.1);
+        let mut seen = vec![0u8; self.obstacles.len()];
+
+        loop {
+            if seen[cell] & direction.bit() != 0 {
+                return true;
+            }
+            seen[cell] |= direction.bit();
+
+            match self.jump(cell, direction, extra) {
+                Some(end) => {
+                    cell = end;
+                    direction = direction.turn_right();
+                }
+                None => return false,
+            }
+        }
+    }
+
+    /// Walks the guard cell by cell from the start, with one optional `extra` obstacle.
+    pub fn path(&self, extra: Option<Position>) -> Path {
+        let extra = extra.map(|position| self.cell(position));
+        let (mut cell, mut direction) = self.start;
+        let mut directions = vec![0u8; self.obstacles.len()];
+        let mut steps = vec![];
+
+        let is_loop = loop {
+            if directions[cell] & direction.bit() != 0 {
+                break true;
+            }
+            directions[cell] |= direction.bit();
+            steps.push((self.position(cell), direction));
+
+            match self.ahead(cell, direction) {
+                None => break false,
+                Some(next) if self.obstacles[next] || Some(next) == extra => {
+                    direction = direction.turn_right();
+                }
+                Some(next) => cell = next,
+            }
+        };
+
+        Path {
+            steps,
+            is_loop,
+            width: self.width,
+            directions,
+        }
+    }
+
+    /// Draws the path like the puzzle: `|` and `-` for cells walked through vertically or
+    /// horizontally, `+` for both, and `O` for the extra obstacle.
+    pub fn render(&self, path: &Path, extra: Option<Position>) -> String {
+        let mut lines = vec![];
+        for row in 0..self.height {
+            let line: String = (0..self.width)
+                .map(|column| {
+                    let position = Position(row, column);
+                    let mask = path.directions(position);
+                    let vertical = Direction::ALL
+                        .iter()
+                        .any(|d| d.is_vertical() && mask & d.bit() != 0);
+                    let horizontal = Direction::ALL
+                        .iter()
+                        .any(|d| !d.is_vertical() && mask & d.bit() != 0);
+
+                    if self.is_obstacle(position) {
+                        '#'
+                    } else if Some(position) == extra {
+                        'O'
+                    } else if self.cell(position) == self.start.0 {
+                        self.start.1.symbol()
+                    } else {
+                        match (vertical, horizontal) {
+                            (true, true) => '+',
+                            (true, false) => '|',
+                            (false, true) => '-',
+                            (false, false) => '.',
+                        }
+                    }
+                })
+                .collect();
+            lines.push(line);
+        }
+        lines.join("\n") + "\n"
+    }
+}
+
+impl FromStr for Lab {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let rows: Vec<&str> = s.lines().filter(|line| !line.is_empty()).collect();
+        let width = rows.first().map_or(0, |row| row.len());
+        if width == 0 || rows.iter().any(|row| row.len() != width) {
+            return Err(Error("lab has to be a non-empty rectangle".to_string()));
+        }
+
+        let mut obstacles = Vec::with_capacity(width * rows.len());
+        let mut start = None;
+        for c in rows.iter().flat_map(|row| row.chars()) {
+            let direction = Direction::ALL.into_iter().find(|d| d.symbol() == c);
+            match (c, direction) {
+                (_, Some(direction)) => start = Some((obstacles.len(), direction)),
+                ('#', _) | ('.', _) => {}
+                _ => return Err(Error(format!("unexpected character \"{c}\""))),
+            }
+            obstacles.push(c == '#');
+        }
+
+        if u32::try_from(obstacles.len()).is_err() {
+            return Err(Error("lab is too large".to_string()));
+        }
+
+        Ok(Lab {
+            width,
+            height: rows.len(),
+            obstacles,
+            start: start.ok_or_else(|| Error("no guard in lab".to_string()))?,
+            jumps: OnceLock::new(),
+        })
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Direction, Lab, Position};
+
+    const EXAMPLE: &str = "\
+....#.....
+.........#
+..........
+..#.......
+.......#..
+..........
+.#..^.....
+........#.
+#.........
+......#...
+";
+
+    #[test]
+    fn walks_path() {
+        let lab: Lab = EXAMPLE.parse().unwrap();
+        let path = lab.path(None);
+        assert!(!path.is_loop);
+        assert_eq!(path.visited(), 41);
+        assert_eq!(path.steps[0], (Position(6, 4), Direction::Up));
+        assert_eq!(
+            path.directions(Position(1, 4)),
+            Direction::Up.bit() | Direction::Right.bit()
+        );
+    }
+
+    #[test]
+    fn renders_loop() {
+        let lab: Lab = EXAMPLE.parse().unwrap();
+        let extra = Some(Position(6, 3));
+        let path = lab.path(extra);
+        assert!(path.is_loop);
+        assert_eq!(
+            lab.render(&path, extra),
+            "\
+....#.....
+....+---+#
+....|...|.
+..#.|...|.
+....|..#|.
+....|...|.
+.#.O^---+.
+........#.
+#.........
+......#...
+"
+        );
+    }
+
+    #[test]
+    fn detects_loops_with_jumps() {
+        let lab: Lab = EXAMPLE.parse().unwrap();
+        assert!(!lab.has_loop(lab.start(), None));
+
+        let loops: Vec<Position> = (0..lab.height())
+            .flat_map(|row| (0..lab.width()).map(move |column| Position(row, column)))
+            .filter(|&position| position != lab.start().0 && !lab.is_obstacle(position))
+            .filter(|&position| lab.has_loop(lab.start(), Some(position)))
+            .collect();
+        assert_eq!(
+            loops,
+            vec![
+                Position(6, 3),
+                Position(7, 6),
+                Position(7, 7),
+                Position(8, 1),
+                Position(8, 3),
+                Position(9, 7),
+            ]
+        );
+
+        for &position in &loops {
+            assert!(lab.path(Some(position)).is_loop);
+        }
+    }
+
+    #[test]
+    fn rejects_invalid_labs() {
+        assert!("....\n...".parse::<Lab>().is_err());
+        assert!("....\n....".parse::<Lab>().is_err());
+        assert!("..^.\n..x.".parse::<Lab>().is_err());
+    }
+}
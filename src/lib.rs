@@ -1,3 +1,11 @@
+pub mod circuit;
+pub mod computer;
+pub mod dot;
+pub mod equation;
+pub mod frame;
+pub mod graph;
+pub mod guard;
+pub mod linear;
 pub mod template;
 
 // Use this file to add helper functions and additional modules.
@@ -0,0 +1,386 @@
+/// Module for solving small systems of linear equations exactly, e.g. the claw machines of day 13.
+/// All arithmetic is done on `i128` rationals and reports overflows instead of wrapping.
+use std::fmt::Display;
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum Error {
+    Overflow,
+    DivisionByZero,
+    /// The matrix is not square, or does not match the length of the right-hand side.
+    Dimensions,
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::Overflow => write!(f, "arithmetic overflow"),
+            Error::DivisionByZero => write!(f, "division by zero"),
+            Error::Dimensions => write!(f, "system of equations is not square"),
+        }
+    }
+}
+
+/// A fraction in lowest terms with a positive denominator.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Rational {
+    numerator: i128,
+    denominator: i128,
+}
+
+impl Rational {
+    pub fn new(numerator: i128, denominator: i128) -> Result<Self, Error> {
+        if denominator == 0 {
+            return Err(Error::DivisionByZero);
+        }
+
+        let divisor = gcd(numerator, denominator);
+        let sign = denominator.signum();
+        Ok(Rational {
+            numerator: (numerator / divisor)
+                .checked_mul(sign)
+                .ok_or(Error::Overflow)?,
+            denominator: (denominator / divisor)
+                .checked_mul(sign)
+                .ok_or(Error::Overflow)?,
+        })
+    }
+
+    pub fn integer(value: i128) -> Self {
+        Rational {
+            numerator: value,
+            denominator: 1,
+        }
+    }
+
+    pub fn numerator(self) -> i128 {
+        self.numerator
+    }
+
+    pub fn denominator(self) -> i128 {
+        self.denominator
+    }
+
+    pub fn is_zero(self) -> bool {
+        self.numerator == 0
+    }
+
+    /// The value as an integer, or `None` if it has a fractional part.
+    pub fn to_integer(self) -> Option<i128> {
+        (self.denominator == 1).then_some(self.numerator)
+    }
+
+    pub fn checked_add(self, other: Rational) -> Result<Rational, Error> {
+        let numerator = mul(self.numerator, other.denominator)?
+            .checked_add(mul(other.numerator, self.denominator)?)
+            .ok_or(Error::Overflow)?;
+        Rational::new(numerator, mul(self.denominator, other.denominator)?)
+    }
+
+    pub fn checked_sub(self, other: Rational) -> Result<Rational, Error> {
+        let negated = Rational {
+            numerator: other.numerator.checked_neg().ok_or(Error::Overflow)?,
+            denominator: other.denominator,
+        };
+        self.checked_add(negated)
+    }
+
+    pub fn checked_mul(self, other: Rational) -> Result<Rational, Error> {
+        Rational::new(
+            mul(self.numerator, other.numerator)?,
+            mul(self.denominator, other.denominator)?,
+        )
+    }
+
+    pub fn checked_div(self, other: Rational) -> Result<Rational, Error> {
+        Rational::new(
+            mul(self.numerator, other.denominator)?,
+            mul(self.denominator, other.numerator)?,
+        )
+    }
+}
+
+impl Display for Rational {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.to_integer() {
+            Some(value) => write!(f, "{value}"),
+            None => write!(f, "{}/{}", self.numerator, self.denominator),
+        }
+    }
+}
+
+fn mul(a: i128, b: i128) -> Result<i128, Error> {
+    a.checked_mul(b).ok_or(Error::Overflow)
+}
+
+/// Greatest common divisor, which is `1` for two zeros so it can always be divided by.
+fn gcd(a: i128, b: i128) -> i128 {
+    let (mut a, mut b) = (a.unsigned_abs(), b.unsigned_abs());
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    i128::try_from(a.max(1)).unwrap_or(1)
+}
+
+/// Returns `(g, x, y)` with `a * x + b * y == g == gcd(a, b)`.
+fn extended_gcd(a: i128, b: i128) -> (i128, i128, i128) {
+    if b == 0 {
+        return (a.abs(), a.signum(), 0);
+    }
+    let (g, x, y) = extended_gcd(b, a % b);
+    (g, y, x - (a / b) * y)
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Solution {
+    Unique(Vec<Rational>),
+    /// The equations are dependent and have infinitely many solutions.
+    Infinite,
+    Inconsistent,
+}
+
+impl Solution {
+    /// The unique solution, if it exists and all of its values are integers.
+    pub fn integral(&self) -> Option<Vec<i128>> {
+        match self {
+            Solution::Unique(values) => values.iter().map(|value| value.to_integer()).collect(),
+            _ => None,
+        }
+    }
+}
+
+/// Solves `matrix * x = rhs` by Gaussian elimination.
+pub fn solve(matrix: &[Vec<i64>], rhs: &[i64]) -> Result<Solution, Error> {
+    let size = rhs.len();
+    if matrix.len() != size || matrix.iter().any(|row| row.len() != size) {
+        return Err(Error::Dimensions);
+    }
+
+    // augmented matrix with the right-hand side as last column.
+    let mut rows: Vec<Vec<Rational>> = matrix
+        .iter()
+        .zip(rhs)
+        .map(|(row, &value)| {
+            row.iter()
+                .chain([&value])
+                .map(|&v| Rational::integer(i128::from(v)))
+                .collect()
+        })
+        .collect();
+
+    let mut pivot_row = 0;
+    let mut is_singular = false;
+
+    for column in 0..size {
+        let Some(pivot) = (pivot_row..size).find(|&row| !rows[row][column].is_zero()) else {
+            is_singular = true;
+            continue;
+        };
+        rows.swap(pivot_row, pivot);
+
+        let pivot = rows[pivot_row].clone();
+        for (i, row) in rows.iter_mut().enumerate() {
+            if i == pivot_row || row[column].is_zero() {
+                continue;
+            }
+            let factor = row[column].checked_div(pivot[column])?;
+            for (value, &pivot_value) in row.iter_mut().zip(&pivot).skip(column) {
+                *value = value.checked_sub(factor.checked_mul(pivot_value)?)?;
+            }
+        }
+        pivot_row += 1;
+    }
+
+    if is_singular {
+        let is_consistent = rows[pivot_row..].iter().all(|row| row[size].is_zero());
+        return Ok(if is_consistent {
+            Solution::Infinite
+        } else {
+            Solution::Inconsistent
+        });
+    }
+
+    let values = rows
+        .iter()
+        .enumerate()
+        .map(|(i, row)| row[size].checked_div(row[i]))
+        .collect::<Result<_, _>>()?;
+    Ok(Solution::Unique(values))
+}
+
+/// Finds the cheapest non-negative integer presses `[n, m]` with `n * a + m * b == target`,
+/// where each press of `a` and `b` costs `costs[0]` and `costs[1]`.
+///
+/// If `a` and `b` are collinear there can be many combinations. The cost is linear along them,
+/// so the cheapest one sits at an end of the range of valid combinations.
+pub fn cheapest_combination(
+    a: [i64; 2],
+    b: [i64; 2],
+    target: [i64; 2],
+    costs: [i64; 2],
+) -> Result<Option<[i64; 2]>, Error> {
+    let matrix = [vec![a[0], b[0]], vec![a[1], b[1]]];
+
+    let presses = match solve(&matrix, &target)? {
+        Solution::Inconsistent => return Ok(None),
+        Solution::Unique(values) => match (values[0].to_integer(), values[1].to_integer()) {
+            (Some(n), Some(m)) if n >= 0 && m >= 0 => [n, m],
+            _ => return Ok(None),
+        },
+        Solution::Infinite => match cheapest_collinear(a, b, target, costs)? {
+            Some(presses) => presses,
+            None => return Ok(None),
+        },
+    };
+
+    let n = i64::try_from(presses[0]).map_err(|_| Error::Overflow)?;
+    let m = i64::try_from(presses[1]).map_err(|_| Error::Overflow)?;
+    Ok(Some([n, m]))
+}
+
+/// Solves `n * p + m * q == t` along the one axis shared by `a`, `b` and `target`.
+fn cheapest_collinear(
+    a: [i64; 2],
+    b: [i64; 2],
+    target: [i64; 2],
+    costs: [i64; 2],
+) -> Result<Option<[i128; 2]>, Error> {
+    // both components of a collinear vector are zero together, so either axis works if it is
+    // non-zero for one of the buttons.
+    let Some(axis) = (0..2).find(|&i| a[i] != 0 || b[i] != 0) else {
+        return Ok((target == [0, 0]).then_some([0, 0]));
+    };
+    let (p, q, t) = (
+        i128::from(a[axis]),
+        i128::from(b[axis]),
+        i128::from(target[axis]),
+    );
+    let (cost_a, cost_b) = (i128::from(costs[0]), i128::from(costs[1]));
+
+    let (g, x, y) = extended_gcd(p, q);
+    if t % g != 0 {
+        return Ok(None);
+    }
+
+    // all solutions are `n = n0 + k * dn`, `m = m0 + k * dm`.
+    let (n0, m0) = (mul(x, t / g)?, mul(y, t / g)?);
+    let (dn, dm) = (q / g, -p / g);
+
+    // range of `k` for which `n` and `m` stay non-negative.
+    let (mut low, mut high) = (i128::MIN, i128::MAX);
+    for (start, step) in [(n0, dn), (m0, dm)] {
+        match step.signum() {
+            1 => low = low.max((-start).div_euclid(step) + i128::from((-start) % step != 0)),
+            -1 => high = high.min(start.div_euclid(-step)),
+            _ if start < 0 => return Ok(None),
+            _ => {}
+        }
+    }
+    if low > high {
+        return Ok(None);
+    }
+
+    let slope = mul(cost_a, dn)?
+        .checked_add(mul(cost_b, dm)?)
+        .ok_or(Error::Overflow)?;
+    let k = match slope.signum() {
+        1 => low,
+        -1 => high,
+        _ if low != i128::MIN => low,
+        _ => high,
+    };
+    if k == i128::MIN || k == i128::MAX {
+        // the cost decreases without bound, which only happens for negative costs.
+        return Ok(None);
+    }
+
+    let n = n0.checked_add(mul(k, dn)?).ok_or(Error::Overflow)?;
+    let m = m0.checked_add(mul(k, dm)?).ok_or(Error::Overflow)?;
+    Ok(Some([n, m]))
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{cheapest_combination, solve, Error, Rational, Solution};
+
+    #[test]
+    fn reduces_rationals() {
+        let half = Rational::new(-3, -6).unwrap();
+        assert_eq!(half, Rational::new(1, 2).unwrap());
+        assert_eq!(half.to_string(), "1/2");
+        assert_eq!(half.to_integer(), None);
+        assert_eq!(half.checked_add(half).unwrap().to_integer(), Some(1));
+        assert_eq!(Rational::new(1, 0), Err(Error::DivisionByZero));
+        assert_eq!(
+            Rational::integer(i128::MAX).checked_mul(Rational::integer(2)),
+            Err(Error::Overflow)
+        );
+    }
+
+    #[test]
+    fn solves_square_systems() {
+        let solution = solve(
+            &[vec![2, 1, -1], vec![-3, -1, 2], vec![-2, 1, 2]],
+            &[8, -11, -3],
+        )
+        .unwrap();
+        assert_eq!(solution.integral(), Some(vec![2, 3, -1]));
+
+        let solution = solve(&[vec![2, 0], vec![0, 3]], &[1, 1]).unwrap();
+        assert_eq!(solution.integral(), None);
+        assert_eq!(
+            solution,
+            Solution::Unique(vec![
+                Rational::new(1, 2).unwrap(),
+                Rational::new(1, 3).unwrap()
+            ])
+        );
+
+        assert_eq!(
+            solve(&[vec![1, 2], vec![2, 4]], &[3, 7]),
+            Ok(Solution::Inconsistent)
+        );
+        assert_eq!(solve(&[vec![1, 2]], &[3]), Err(Error::Dimensions));
+    }
+
+    #[test]
+    fn finds_claw_machine_presses() {
+        assert_eq!(
+            cheapest_combination([94, 34], [22, 67], [8400, 5400], [3, 1]),
+            Ok(Some([80, 40]))
+        );
+        assert_eq!(
+            cheapest_combination([26, 66], [67, 21], [12748, 12176], [3, 1]),
+            Ok(None)
+        );
+        assert_eq!(
+            cheapest_combination([26, 66], [67, 21], [10000000012748, 10000000012176], [3, 1]),
+            Ok(Some([118679050709, 103199174542]))
+        );
+    }
+
+    #[test]
+    fn minimizes_collinear_presses() {
+        // b is cheaper per distance, so as many b presses as possible.
+        assert_eq!(
+            cheapest_combination([3, 3], [1, 1], [10, 10], [4, 1]),
+            Ok(Some([0, 10]))
+        );
+        // a is cheaper per distance.
+        assert_eq!(
+            cheapest_combination([4, 2], [2, 1], [10, 5], [1, 1]),
+            Ok(Some([2, 1]))
+        );
+        assert_eq!(
+            cheapest_combination([4, 2], [6, 3], [10, 5], [3, 1]),
+            Ok(Some([1, 1]))
+        );
+        assert_eq!(
+            cheapest_combination([4, 2], [6, 3], [3, 1], [3, 1]),
+            Ok(None)
+        );
+        assert_eq!(
+            cheapest_combination([4, 2], [6, 3], [5, 5], [3, 1]),
+            Ok(None)
+        );
+    }
+}
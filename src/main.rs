@@ -1,14 +1,173 @@
-use advent_of_code::template::commands::{all, download, read, scaffold, solve, time};
+use advent_of_code::template::commands::{
+    all, download, read, readme, scaffold, solve, stars, time, verify, watch_day,
+};
 use args::{parse, AppArguments};
 
+#[cfg(feature = "encrypted-inputs")]
+use advent_of_code::template::commands::inputs;
 #[cfg(feature = "today")]
 use advent_of_code::template::Day;
 #[cfg(feature = "today")]
 use std::process;
 
 mod args {
-    use advent_of_code::template::Day;
-    use std::process;
+    use advent_of_code::template::cli::{self, Arg, Command, Error, Matches, Program};
+    use advent_of_code::template::commands::time::{ThreadCounts, TimeOptions};
+    use advent_of_code::template::runner::{self, RunOptions};
+    use advent_of_code::template::{Day, DaySet};
+
+    static DAYS_HELP: &str =
+        "Days to run, e.g. `1-5,9`, `unsolved`, `slow` or `changed`. Defaults to all days.";
+
+    /// `solve` accepts the arguments of solution binaries and passes them down.
+    static SOLVE_ARGS: [Arg; 4 + runner::RUN_ARGS.len()] = cli::concat(
+        &[
+            Arg::positional("DAY", "The day to run."),
+            Arg::flag("--release", "Run an optimized build."),
+            Arg::flag("--dhat", "Profile heap allocations with dhat."),
+            Arg::flag(
+                "--allocs",
+                "Count allocations with a lightweight allocator.",
+            ),
+        ],
+        &runner::RUN_ARGS,
+    );
+
+    static PROGRAM: Program = Program {
+        about: "🎄 Advent of Code solutions in Rust.",
+        commands: &[
+            Command {
+                name: "scaffold",
+                about: "Create the solution, input and example files for a day.",
+                args: &[
+                    Arg::positional("DAY", "The day to scaffold."),
+                    Arg::flag("--download", "Download the input and puzzle afterwards."),
+                    Arg::flag("--overwrite", "Overwrite an existing solution file."),
+                ],
+            },
+            Command {
+                name: "download",
+                about: "Download the input and puzzle description for a day via aoc-cli.",
+                args: &[Arg::positional("DAY", "The day to download.")],
+            },
+            Command {
+                name: "read",
+                about: "Read the puzzle description for a day in the terminal via aoc-cli.",
+                args: &[Arg::positional("DAY", "The day to read.")],
+            },
+            Command {
+                name: "solve",
+                about: "Run the solution for a day.",
+                args: &SOLVE_ARGS,
+            },
+            Command {
+                name: "watch-day",
+                about: "Re-run the example tests and the solution for a day when its files change.",
+                args: &[
+                    Arg::positional("DAY", "The day to watch."),
+                    Arg::flag("--release", "Run optimized builds."),
+                ],
+            },
+            Command {
+                name: "all",
+                about: "Run the solutions for several days.",
+                args: &[
+                    Arg::optional("DAYS", DAYS_HELP),
+                    Arg::flag("--release", "Run optimized builds."),
+                ],
+            },
+            Command {
+                name: "verify",
+                about: "Check the answers of several days against the answer ledger.",
+                args: &[
+                    Arg::optional("DAYS", DAYS_HELP),
+                    Arg::flag("--release", "Run optimized builds."),
+                    Arg::flag("--store", "Record the current answers in the ledger."),
+                ],
+            },
+            Command {
+                name: "time",
+                about: "Benchmark solutions.",
+                args: &[
+                    Arg::optional("DAYS", "Days to benchmark. Defaults to `unsolved`."),
+                    Arg::flag("--all", "Benchmark all days."),
+                    Arg::flag("--store", "Store timings and update the README."),
+                    Arg::option(
+                        "--slow-threshold",
+                        "MS",
+                        "Store the threshold above which days are considered `slow`.",
+                    ),
+                    Arg::option(
+                        "--report",
+                        "FORMAT",
+                        "Write a report as `csv`, `jsonl`, `md` or `html`.",
+                    ),
+                    Arg::option(
+                        "--out",
+                        "PATH",
+                        "File to write the report to. Defaults to stdout.",
+                    ),
+                    Arg::flag(
+                        "--dhat",
+                        "Collect allocation stats with dhat. Only these are stored.",
+                    ),
+                    Arg::flag(
+                        "--allocs",
+                        "Collect allocation stats with the counting allocator. Only these are stored.",
+                    ),
+                    Arg::option(
+                        "--threads",
+                        "COUNTS",
+                        "Run with each rayon pool size, e.g. `1,2,4,8`, and print a scaling table.",
+                    ),
+                    Arg::flag(
+                        "--single-threaded",
+                        "Run with a single rayon thread for fair comparisons.",
+                    ),
+                    Arg::flag(
+                        "--perf",
+                        "Collect hardware counters of the main thread via perf_event_open (Linux only).",
+                    ),
+                    Arg::option(
+                        "--compare",
+                        "BASELINE",
+                        "Compare against a timings file or the timings at a git revision.",
+                    ),
+                ],
+            },
+            Command {
+                name: "readme",
+                about: "Update all generated README sections from stored timings and answers.",
+                args: &[Arg::flag(
+                    "--check",
+                    "Fail instead of writing if the README is out of date.",
+                )],
+            },
+            Command {
+                name: "stars",
+                about: "Update the stars table in the README from the answer ledger.",
+                args: &[Arg::flag(
+                    "--run",
+                    "Run all solutions and count parts that match the ledger as well.",
+                )],
+            },
+            #[cfg(feature = "encrypted-inputs")]
+            Command {
+                name: "inputs",
+                about: "Pack, unpack or verify the encrypted input archive.",
+                args: &[Arg::positional(
+                    "ACTION",
+                    "One of `pack`, `unpack` or `verify`.",
+                )],
+            },
+            #[cfg(feature = "today")]
+            Command {
+                name: "today",
+                about: "Scaffold, download and read the current day.",
+                args: &[],
+            },
+        ],
+    };
 
     pub enum AppArguments {
         Download {
@@ -26,87 +185,147 @@ mod args {
             day: Day,
             release: bool,
             dhat: bool,
-            submit: Option<u8>,
+            allocs: bool,
+            options: RunOptions,
+        },
+        WatchDay {
+            day: Day,
+            release: bool,
         },
         All {
+            days: DaySet,
             release: bool,
         },
-        Time {
-            all: bool,
-            day: Option<Day>,
+        Verify {
+            days: DaySet,
+            release: bool,
             store: bool,
         },
+        Time(TimeOptions),
+        Readme {
+            check: bool,
+        },
+        Stars {
+            run: bool,
+        },
+        #[cfg(feature = "encrypted-inputs")]
+        Inputs {
+            action: advent_of_code::template::commands::inputs::Action,
+        },
         #[cfg(feature = "today")]
         Today,
     }
 
-    pub fn parse() -> Result<AppArguments, Box<dyn std::error::Error>> {
-        let mut args = pico_args::Arguments::from_env();
+    pub fn parse() -> Result<AppArguments, Error> {
+        let args: Vec<String> = std::env::args().skip(1).collect();
+        let (command, matches) = PROGRAM.parse(&args)?;
 
-        let app_args = match args.subcommand()?.as_deref() {
-            Some("all") => AppArguments::All {
-                release: args.contains("--release"),
+        let app_args = match command.name {
+            "all" => AppArguments::All {
+                days: matches.parse("DAYS")?.unwrap_or_else(DaySet::all),
+                release: matches.flag("--release"),
             },
-            Some("time") => {
-                let all = args.contains("--all");
-                let store = args.contains("--store");
-
-                AppArguments::Time {
-                    all,
-                    day: args.opt_free_from_str()?,
-                    store,
-                }
-            }
-            Some("download") => AppArguments::Download {
-                day: args.free_from_str()?,
+            "watch-day" => AppArguments::WatchDay {
+                day: matches.parse_required("DAY")?,
+                release: matches.flag("--release"),
+            },
+            "verify" => AppArguments::Verify {
+                days: matches.parse("DAYS")?.unwrap_or_else(DaySet::all),
+                release: matches.flag("--release"),
+                store: matches.flag("--store"),
             },
-            Some("read") => AppArguments::Read {
-                day: args.free_from_str()?,
+            "time" => AppArguments::Time(TimeOptions {
+                days: matches.parse("DAYS")?,
+                run_all: matches.flag("--all"),
+                store: matches.flag("--store"),
+                slow_threshold: matches.parse("--slow-threshold")?,
+                report: matches.parse("--report")?,
+                out: matches.value("--out").map(String::from),
+                compare: matches.value("--compare").map(String::from),
+                dhat: matches.flag("--dhat"),
+                allocs: matches.flag("--allocs"),
+                perf: matches.flag("--perf"),
+                threads: parse_threads(&matches)?,
+            }),
+            "download" => AppArguments::Download {
+                day: matches.parse_required("DAY")?,
             },
-            Some("scaffold") => AppArguments::Scaffold {
-                day: args.free_from_str()?,
-                download: args.contains("--download"),
-                overwrite: args.contains("--overwrite"),
+            "read" => AppArguments::Read {
+                day: matches.parse_required("DAY")?,
             },
-            Some("solve") => AppArguments::Solve {
-                day: args.free_from_str()?,
-                release: args.contains("--release"),
-                submit: args.opt_value_from_str("--submit")?,
-                dhat: args.contains("--dhat"),
+            "scaffold" => AppArguments::Scaffold {
+                day: matches.parse_required("DAY")?,
+                download: matches.flag("--download"),
+                overwrite: matches.flag("--overwrite"),
+            },
+            "solve" => AppArguments::Solve {
+                day: matches.parse_required("DAY")?,
+                release: matches.flag("--release"),
+                dhat: matches.flag("--dhat"),
+                allocs: matches.flag("--allocs"),
+                options: RunOptions::from_matches(&matches)?,
+            },
+            "readme" => AppArguments::Readme {
+                check: matches.flag("--check"),
+            },
+            "stars" => AppArguments::Stars {
+                run: matches.flag("--run"),
+            },
+            #[cfg(feature = "encrypted-inputs")]
+            "inputs" => AppArguments::Inputs {
+                action: matches.parse_required("ACTION")?,
             },
             #[cfg(feature = "today")]
-            Some("today") => AppArguments::Today,
-            Some(x) => {
-                eprintln!("Unknown command: {x}");
-                process::exit(1);
-            }
-            None => {
-                eprintln!("No command specified.");
-                process::exit(1);
-            }
+            "today" => AppArguments::Today,
+            name => unreachable!("command `{name}` is defined but not handled"),
         };
 
-        let remaining = args.finish();
-        if !remaining.is_empty() {
-            eprintln!("Warning: unknown argument(s): {remaining:?}.");
+        Ok(app_args)
+    }
+
+    fn parse_threads(matches: &Matches<'_>) -> Result<Option<ThreadCounts>, Error> {
+        let threads = matches.parse("--threads")?;
+
+        if !matches.flag("--single-threaded") {
+            return Ok(threads);
         }
 
-        Ok(app_args)
+        match threads {
+            Some(_) => Err(Error::InvalidValue {
+                name: "--threads",
+                value: matches.value("--threads").unwrap_or_default().into(),
+                reason: "cannot be combined with `--single-threaded`".into(),
+            }),
+            None => Ok(Some(ThreadCounts(vec![1]))),
+        }
     }
 }
 
 fn main() {
     match parse() {
+        Err(advent_of_code::template::cli::Error::Help(help)) => {
+            println!("{help}");
+        }
         Err(err) => {
             eprintln!("Error: {err}");
             std::process::exit(1);
         }
         Ok(args) => match args {
-            AppArguments::All { release } => all::handle(release),
-            AppArguments::Time { day, all, store } => time::handle(day, all, store),
+            AppArguments::All { days, release } => all::handle(&days, release),
+            AppArguments::WatchDay { day, release } => watch_day::handle(day, release),
+            AppArguments::Verify {
+                days,
+                release,
+                store,
+            } => verify::handle(&days, release, store),
+            AppArguments::Time(options) => time::handle(options),
             AppArguments::Download { day } => download::handle(day),
             AppArguments::Read { day } => read::handle(day),
-            AppArguments::Scaffold { day, download, overwrite } => {
+            AppArguments::Scaffold {
+                day,
+                download,
+                overwrite,
+            } => {
                 scaffold::handle(day, overwrite);
                 if download {
                     download::handle(day);
@@ -116,8 +335,13 @@ fn main() {
                 day,
                 release,
                 dhat,
-                submit,
-            } => solve::handle(day, release, dhat, submit),
+                allocs,
+                options,
+            } => solve::handle(day, release, dhat, allocs, &options),
+            AppArguments::Readme { check } => readme::handle(check),
+            AppArguments::Stars { run } => stars::handle(run),
+            #[cfg(feature = "encrypted-inputs")]
+            AppArguments::Inputs { action } => inputs::handle(action),
             #[cfg(feature = "today")]
             AppArguments::Today => {
                 match Day::today() {
@@ -0,0 +1,207 @@
+/// Allocation statistics of solution parts, as collected by the heap profiler or the counting allocator.
+use std::fmt::Display;
+
+#[cfg(feature = "count-allocs")]
+pub use counting::CountingAlloc;
+
+/// Heap usage of a single run of a solution part.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct AllocStats {
+    pub total_bytes: u64,
+    pub total_blocks: u64,
+    pub peak_bytes: u64,
+    pub peak_blocks: u64,
+}
+
+#[cfg(feature = "dhat-heap")]
+impl From<dhat::HeapStats> for AllocStats {
+    fn from(stats: dhat::HeapStats) -> Self {
+        AllocStats {
+            total_bytes: stats.total_bytes,
+            total_blocks: stats.total_blocks,
+            peak_bytes: stats.max_bytes as u64,
+            peak_blocks: stats.max_blocks as u64,
+        }
+    }
+}
+
+impl Display for AllocStats {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} in {} allocs, {} peak in {} blocks",
+            format_bytes(self.total_bytes),
+            self.total_blocks,
+            format_bytes(self.peak_bytes),
+            self.peak_blocks
+        )
+    }
+}
+
+impl AllocStats {
+    /// Parses the format of the `Display` impl, e.g. `2.1MB in 340 allocs, 1.0MB peak in 12 blocks`.
+    pub fn parse(s: &str) -> Option<Self> {
+        let (total, peak) = s.split_once(", ")?;
+        let (total_bytes, total_blocks) = total.trim().split_once(" in ")?;
+        let (peak_bytes, peak_blocks) = peak.trim().split_once(" peak in ")?;
+
+        Some(AllocStats {
+            total_bytes: parse_bytes(total_bytes)?,
+            total_blocks: total_blocks.strip_suffix(" allocs")?.parse().ok()?,
+            peak_bytes: parse_bytes(peak_bytes)?,
+            peak_blocks: peak_blocks.strip_suffix(" blocks")?.parse().ok()?,
+        })
+    }
+}
+
+static UNITS: [(&str, u64); 4] = [
+    ("GB", 1_000_000_000),
+    ("MB", 1_000_000),
+    ("KB", 1_000),
+    ("B", 1),
+];
+
+#[allow(clippy::cast_precision_loss)]
+pub fn format_bytes(bytes: u64) -> String {
+    match UNITS.iter().find(|(_, size)| bytes >= *size) {
+        Some((unit, 1)) => format!("{bytes}{unit}"),
+        Some((unit, size)) => format!("{:.1}{unit}", bytes as f64 / *size as f64),
+        None => "0B".into(),
+    }
+}
+
+#[allow(
+    clippy::cast_possible_truncation,
+    clippy::cast_sign_loss,
+    clippy::cast_precision_loss
+)]
+fn parse_bytes(s: &str) -> Option<u64> {
+    let (unit, size) = UNITS.iter().find(|(unit, _)| s.ends_with(unit))?;
+    let value: f64 = s.strip_suffix(unit)?.parse().ok()?;
+    Some((value * *size as f64).round() as u64)
+}
+
+/// A global allocator wrapping the system allocator, counting allocations of the current part.
+/// Cheaper than dhat and does not need a separate profile, but only reports totals and peaks.
+#[cfg(feature = "count-allocs")]
+mod counting {
+    use std::alloc::{GlobalAlloc, Layout, System};
+    use std::sync::atomic::{AtomicU64, Ordering::Relaxed};
+
+    use super::AllocStats;
+
+    static TOTAL_BYTES: AtomicU64 = AtomicU64::new(0);
+    static TOTAL_BLOCKS: AtomicU64 = AtomicU64::new(0);
+    static CURRENT_BYTES: AtomicU64 = AtomicU64::new(0);
+    static CURRENT_BLOCKS: AtomicU64 = AtomicU64::new(0);
+    static PEAK_BYTES: AtomicU64 = AtomicU64::new(0);
+    static PEAK_BLOCKS: AtomicU64 = AtomicU64::new(0);
+    static BASE_BYTES: AtomicU64 = AtomicU64::new(0);
+    static BASE_BLOCKS: AtomicU64 = AtomicU64::new(0);
+
+    pub struct CountingAlloc;
+
+    impl CountingAlloc {
+        /// Starts a new measurement. Memory allocated before is not counted towards the peak.
+        pub fn reset() {
+            let bytes = CURRENT_BYTES.load(Relaxed);
+            let blocks = CURRENT_BLOCKS.load(Relaxed);
+
+            TOTAL_BYTES.store(0, Relaxed);
+            TOTAL_BLOCKS.store(0, Relaxed);
+            PEAK_BYTES.store(bytes, Relaxed);
+            PEAK_BLOCKS.store(blocks, Relaxed);
+            BASE_BYTES.store(bytes, Relaxed);
+            BASE_BLOCKS.store(blocks, Relaxed);
+        }
+
+        /// Allocations since the last [`CountingAlloc::reset`].
+        pub fn stats() -> AllocStats {
+            AllocStats {
+                total_bytes: TOTAL_BYTES.load(Relaxed),
+                total_blocks: TOTAL_BLOCKS.load(Relaxed),
+                peak_bytes: PEAK_BYTES
+                    .load(Relaxed)
+                    .saturating_sub(BASE_BYTES.load(Relaxed)),
+                peak_blocks: PEAK_BLOCKS
+                    .load(Relaxed)
+                    .saturating_sub(BASE_BLOCKS.load(Relaxed)),
+            }
+        }
+    }
+
+    fn record_alloc(size: usize) {
+        let size = size as u64;
+        TOTAL_BYTES.fetch_add(size, Relaxed);
+        TOTAL_BLOCKS.fetch_add(1, Relaxed);
+
+        let bytes = CURRENT_BYTES.fetch_add(size, Relaxed) + size;
+        PEAK_BYTES.fetch_max(bytes, Relaxed);
+
+        let blocks = CURRENT_BLOCKS.fetch_add(1, Relaxed) + 1;
+        PEAK_BLOCKS.fetch_max(blocks, Relaxed);
+    }
+
+    fn record_dealloc(size: usize) {
+        CURRENT_BYTES.fetch_sub(size as u64, Relaxed);
+        CURRENT_BLOCKS.fetch_sub(1, Relaxed);
+    }
+
+    unsafe impl GlobalAlloc for CountingAlloc {
+        unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+            let ptr = System.alloc(layout);
+            if !ptr.is_null() {
+                record_alloc(layout.size());
+            }
+            ptr
+        }
+
+        unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+            let ptr = System.alloc_zeroed(layout);
+            if !ptr.is_null() {
+                record_alloc(layout.size());
+            }
+            ptr
+        }
+
+        unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+            System.dealloc(ptr, layout);
+            record_dealloc(layout.size());
+        }
+
+        unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+            let new_ptr = System.realloc(ptr, layout, new_size);
+            if !new_ptr.is_null() {
+                record_dealloc(layout.size());
+                record_alloc(new_size);
+            }
+            new_ptr
+        }
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{format_bytes, AllocStats};
+
+    #[test]
+    fn formats_bytes() {
+        assert_eq!(format_bytes(0), "0B");
+        assert_eq!(format_bytes(999), "999B");
+        assert_eq!(format_bytes(2_148_000), "2.1MB");
+        assert_eq!(format_bytes(3_000_000_000), "3.0GB");
+    }
+
+    #[test]
+    fn roundtrips_summary() {
+        let stats = AllocStats {
+            total_bytes: 2_100_000,
+            total_blocks: 340,
+            peak_bytes: 512,
+            peak_blocks: 12,
+        };
+        let summary = stats.to_string();
+        assert_eq!(summary, "2.1MB in 340 allocs, 512B peak in 12 blocks");
+        assert_eq!(AllocStats::parse(&summary), Some(stats));
+    }
+}
@@ -0,0 +1,328 @@
+/// Module that handles the encrypted input archive and the checksum manifest.
+/// Inputs are packed into a single archive that can be committed, while the plain text files stay git-ignored.
+use std::{collections::BTreeMap, fmt::Display, fs, io};
+
+use sha2::{Digest, Sha256};
+
+pub static MANIFEST_PATH: &str = "./data/inputs.sha256";
+
+pub static ARCHIVE_PATH: &str = "./data/inputs.enc";
+
+#[derive(Debug)]
+pub enum Error {
+    Parser(String),
+    IO(io::Error),
+    #[cfg(not(feature = "encrypted-inputs"))]
+    Disabled,
+    #[cfg(feature = "encrypted-inputs")]
+    Key(String),
+    #[cfg(feature = "encrypted-inputs")]
+    Decrypt,
+}
+
+impl From<std::io::Error> for Error {
+    fn from(e: std::io::Error) -> Self {
+        Error::IO(e)
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::Parser(s) => write!(f, "{s}"),
+            Error::IO(e) => write!(f, "{e}"),
+            #[cfg(not(feature = "encrypted-inputs"))]
+            Error::Disabled => write!(
+                f,
+                "\"{ARCHIVE_PATH}\" can only be read with the `encrypted-inputs` feature."
+            ),
+            #[cfg(feature = "encrypted-inputs")]
+            Error::Key(s) => write!(f, "{s}"),
+            #[cfg(feature = "encrypted-inputs")]
+            Error::Decrypt => write!(
+                f,
+                "could not decrypt \"{ARCHIVE_PATH}\". The key is wrong or the archive is corrupted."
+            ),
+        }
+    }
+}
+
+/// Hex encoded SHA-256 digest of a (normalized) input.
+#[must_use]
+pub fn checksum(input: &str) -> String {
+    format!("{:x}", Sha256::digest(input.as_bytes()))
+}
+
+/// Checksums of all packed inputs, keyed by file name.
+/// Stored in the format of `sha256sum`, i.e. `<digest>  <file name>` per line.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Manifest {
+    pub entries: BTreeMap<String, String>,
+}
+
+impl Manifest {
+    /// Builds a manifest for a set of inputs.
+    #[cfg(feature = "encrypted-inputs")]
+    pub fn from_inputs(inputs: &BTreeMap<String, String>) -> Self {
+        Manifest {
+            entries: inputs
+                .iter()
+                .map(|(name, input)| (name.clone(), checksum(input)))
+                .collect(),
+        }
+    }
+
+    /// Rehydrate the manifest from its file. Returns `None` if it is not present or malformed.
+    pub fn read_from_file() -> Option<Self> {
+        let s = match fs::read_to_string(MANIFEST_PATH) {
+            Ok(s) => s,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return None,
+            Err(e) => {
+                eprintln!("could not read \"{MANIFEST_PATH}\": {e}");
+                return None;
+            }
+        };
+
+        match Manifest::try_from(s.as_str()) {
+            Ok(manifest) => Some(manifest),
+            Err(e) => {
+                eprintln!("{e}");
+                None
+            }
+        }
+    }
+
+    /// Dehydrate the manifest to its file.
+    #[cfg(feature = "encrypted-inputs")]
+    pub fn store_file(&self) -> Result<(), io::Error> {
+        fs::write(MANIFEST_PATH, self.to_string())
+    }
+
+    /// Returns `Some(true)` if the input matches its stored checksum, `None` if the manifest has no entry for it.
+    pub fn verify(&self, name: &str, input: &str) -> Option<bool> {
+        self.entries
+            .get(name)
+            .map(|digest| *digest == checksum(input))
+    }
+}
+
+impl Display for Manifest {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (name, digest) in &self.entries {
+            writeln!(f, "{digest}  {name}")?;
+        }
+        Ok(())
+    }
+}
+
+impl TryFrom<&str> for Manifest {
+    type Error = Error;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        let entries = value
+            .lines()
+            .filter(|l| !l.trim().is_empty())
+            .map(|l| {
+                l.split_once("  ")
+                    .filter(|(digest, name)| digest.len() == 64 && !name.is_empty())
+                    .map(|(digest, name)| (name.to_string(), digest.to_string()))
+                    .ok_or_else(|| {
+                        Error::Parser(format!("malformed line in \"{MANIFEST_PATH}\": {l}"))
+                    })
+            })
+            .collect::<Result<_, _>>()?;
+
+        Ok(Manifest { entries })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Encryption of the input archive with ChaCha20-Poly1305.
+///
+/// The key is the SHA-256 digest of a passphrase read from `AOC_INPUTS_KEY`, or from the file at
+/// `AOC_INPUTS_KEYFILE` (defaulting to `.aoc-inputs.key`). The nonce is derived from the key and the
+/// plain text, so packing unchanged inputs produces an identical archive and no git diff.
+///
+/// The plain text is a sequence of `<file name> <byte length>\n<content>` entries.
+#[cfg(feature = "encrypted-inputs")]
+mod crypto {
+    use std::{collections::BTreeMap, env, fs};
+
+    use chacha20poly1305::{
+        aead::{Aead, KeyInit},
+        ChaCha20Poly1305, Key, Nonce,
+    };
+    use sha2::{Digest, Sha256};
+
+    use super::{Error, ARCHIVE_PATH};
+
+    static MAGIC: &[u8] = b"AOCINPUTS1";
+    static DEFAULT_KEYFILE_PATH: &str = "./.aoc-inputs.key";
+    const NONCE_LEN: usize = 12;
+
+    fn load_key() -> Result<Key, Error> {
+        let passphrase = match env::var("AOC_INPUTS_KEY") {
+            Ok(key) => key,
+            Err(_) => {
+                let path =
+                    env::var("AOC_INPUTS_KEYFILE").unwrap_or_else(|_| DEFAULT_KEYFILE_PATH.into());
+                fs::read_to_string(&path).map_err(|e| {
+                    Error::Key(format!(
+                        "no key found. Set `AOC_INPUTS_KEY` or provide a keyfile at \"{path}\" ({e})."
+                    ))
+                })?
+            }
+        };
+
+        let passphrase = passphrase.trim();
+        if passphrase.is_empty() {
+            return Err(Error::Key("the input archive key is empty.".into()));
+        }
+
+        Ok(Sha256::digest(passphrase.as_bytes()))
+    }
+
+    pub fn encrypt(inputs: &BTreeMap<String, String>) -> Result<Vec<u8>, Error> {
+        let key = load_key()?;
+
+        let plaintext: String = inputs
+            .iter()
+            .map(|(name, input)| format!("{name} {}\n{input}", input.len()))
+            .collect();
+
+        let nonce_digest = Sha256::new()
+            .chain_update(key)
+            .chain_update(plaintext.as_bytes())
+            .finalize();
+        let nonce = Nonce::from_slice(&nonce_digest[..NONCE_LEN]);
+
+        let ciphertext = ChaCha20Poly1305::new(&key)
+            .encrypt(nonce, plaintext.as_bytes())
+            .map_err(|_| Error::Parser("could not encrypt inputs.".into()))?;
+
+        let mut archive = MAGIC.to_vec();
+        archive.extend_from_slice(nonce);
+        archive.extend(ciphertext);
+        Ok(archive)
+    }
+
+    pub fn decrypt(archive: &[u8]) -> Result<BTreeMap<String, String>, Error> {
+        let rest = archive
+            .strip_prefix(MAGIC)
+            .ok_or_else(|| Error::Parser(format!("\"{ARCHIVE_PATH}\" is not an input archive.")))?;
+
+        if rest.len() < NONCE_LEN {
+            return Err(Error::Decrypt);
+        }
+
+        let (nonce, ciphertext) = rest.split_at(NONCE_LEN);
+        let plaintext = ChaCha20Poly1305::new(&load_key()?)
+            .decrypt(Nonce::from_slice(nonce), ciphertext)
+            .map_err(|_| Error::Decrypt)?;
+
+        let plaintext = String::from_utf8(plaintext).map_err(|_| Error::Decrypt)?;
+        parse_entries(&plaintext)
+            .ok_or_else(|| Error::Parser(format!("\"{ARCHIVE_PATH}\" contains malformed entries.")))
+    }
+
+    fn parse_entries(mut s: &str) -> Option<BTreeMap<String, String>> {
+        let mut inputs = BTreeMap::new();
+
+        while !s.is_empty() {
+            let (header, rest) = s.split_once('\n')?;
+            let (name, len) = header.rsplit_once(' ')?;
+            let len: usize = len.parse().ok()?;
+            inputs.insert(name.to_string(), rest.get(..len)?.to_string());
+            s = rest.get(len..)?;
+        }
+
+        Some(inputs)
+    }
+}
+
+#[cfg(feature = "encrypted-inputs")]
+pub use crypto::{decrypt, encrypt};
+
+/// Without the feature, an existing archive is reported as unreadable rather than skipped.
+#[cfg(not(feature = "encrypted-inputs"))]
+pub fn decrypt(_archive: &[u8]) -> Result<BTreeMap<String, String>, Error> {
+    Err(Error::Disabled)
+}
+
+/// Reads and decrypts the input archive.
+pub fn read_archive() -> Result<BTreeMap<String, String>, Error> {
+    decrypt(&fs::read(ARCHIVE_PATH)?)
+}
+
+/// Encrypts a set of inputs and writes them to the input archive.
+#[cfg(feature = "encrypted-inputs")]
+pub fn write_archive(inputs: &BTreeMap<String, String>) -> Result<(), Error> {
+    fs::write(ARCHIVE_PATH, encrypt(inputs)?)?;
+    Ok(())
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use super::{checksum, Manifest};
+
+    fn get_mock_inputs() -> BTreeMap<String, String> {
+        BTreeMap::from([
+            ("01.txt".to_string(), "3   4\n4   3\n".to_string()),
+            ("02.txt".to_string(), "7 6 4 2 1\n".to_string()),
+        ])
+    }
+
+    fn get_mock_manifest() -> Manifest {
+        Manifest {
+            entries: get_mock_inputs()
+                .into_iter()
+                .map(|(name, input)| (name, checksum(&input)))
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn computes_checksums() {
+        assert_eq!(
+            checksum(""),
+            "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855"
+        );
+    }
+
+    #[test]
+    fn roundtrips_manifest() {
+        let manifest = get_mock_manifest();
+        let parsed = Manifest::try_from(manifest.to_string().as_str()).unwrap();
+        assert_eq!(manifest, parsed);
+        assert_eq!(parsed.entries.len(), 2);
+    }
+
+    #[test]
+    fn verifies_inputs() {
+        let manifest = get_mock_manifest();
+        assert_eq!(manifest.verify("02.txt", "7 6 4 2 1\n"), Some(true));
+        assert_eq!(manifest.verify("02.txt", "7 6 4 2 2\n"), Some(false));
+        assert_eq!(manifest.verify("03.txt", ""), None);
+    }
+
+    #[test]
+    #[should_panic]
+    fn panics_for_malformed_manifest() {
+        Manifest::try_from("not a checksum line").unwrap();
+    }
+
+    #[cfg(feature = "encrypted-inputs")]
+    #[test]
+    fn roundtrips_archive() {
+        std::env::set_var("AOC_INPUTS_KEY", "hunter2");
+        let inputs = get_mock_inputs();
+        let archive = super::encrypt(&inputs).unwrap();
+        assert_eq!(archive, super::encrypt(&inputs).unwrap());
+        assert_eq!(super::decrypt(&archive).unwrap(), inputs);
+    }
+}
@@ -0,0 +1,431 @@
+/// Minimal declarative command-line parsing.
+/// Commands are described by static [`Command`] definitions, which are used both to validate
+/// arguments and to render `--help` output.
+use std::{collections::HashMap, fmt::Display, str::FromStr};
+
+/// A single argument of a [`Command`]: either a `--flag`, an `--option <VALUE>` or a positional `<VALUE>`.
+#[derive(Clone, Copy, Debug)]
+pub struct Arg {
+    pub name: &'static str,
+    pub value_name: Option<&'static str>,
+    pub help: &'static str,
+    pub required: bool,
+}
+
+impl Arg {
+    /// A boolean `--flag`.
+    pub const fn flag(name: &'static str, help: &'static str) -> Self {
+        Self {
+            name,
+            value_name: None,
+            help,
+            required: false,
+        }
+    }
+
+    /// An `--option <VALUE>` that takes a value.
+    pub const fn option(name: &'static str, value_name: &'static str, help: &'static str) -> Self {
+        Self {
+            name,
+            value_name: Some(value_name),
+            help,
+            required: false,
+        }
+    }
+
+    /// A required positional `<VALUE>`.
+    pub const fn positional(value_name: &'static str, help: &'static str) -> Self {
+        Self {
+            name: value_name,
+            value_name: Some(value_name),
+            help,
+            required: true,
+        }
+    }
+
+    /// An optional positional `[VALUE]`.
+    pub const fn optional(value_name: &'static str, help: &'static str) -> Self {
+        Self {
+            name: value_name,
+            value_name: Some(value_name),
+            help,
+            required: false,
+        }
+    }
+
+    fn is_positional(&self) -> bool {
+        !self.name.starts_with('-')
+    }
+
+    fn usage(&self) -> String {
+        match (self.is_positional(), self.value_name) {
+            (true, _) if self.required => format!("<{}>", self.name),
+            (true, _) => format!("[{}]", self.name),
+            (false, Some(value_name)) => format!("{} <{value_name}>", self.name),
+            (false, None) => self.name.to_string(),
+        }
+    }
+}
+
+/// Joins two argument lists at compile time, e.g. to let a command accept the arguments of another.
+/// `N` must be the combined length.
+pub const fn concat<const N: usize>(first: &[Arg], second: &[Arg]) -> [Arg; N] {
+    assert!(
+        first.len() + second.len() == N,
+        "`N` must be the combined length"
+    );
+
+    let mut args = [Arg::flag("", ""); N];
+    let mut i = 0;
+    while i < N {
+        args[i] = if i < first.len() {
+            first[i]
+        } else {
+            second[i - first.len()]
+        };
+        i += 1;
+    }
+    args
+}
+
+/// A (sub)command definition.
+#[derive(Debug)]
+pub struct Command {
+    pub name: &'static str,
+    pub about: &'static str,
+    pub args: &'static [Arg],
+}
+
+impl Command {
+    /// Parses arguments against this definition. Returns [`Error::Help`] if `--help` was passed.
+    pub fn parse<S: AsRef<str>>(&self, args: &[S]) -> Result<Matches<'_>, Error> {
+        let mut matches = Matches::default();
+        let mut positionals = self.args.iter().filter(|a| a.is_positional());
+        let mut args = args.iter().map(AsRef::as_ref);
+
+        while let Some(raw) = args.next() {
+            if raw == "--help" || raw == "-h" {
+                return Err(Error::Help(self.help()));
+            }
+
+            if raw.starts_with("--") {
+                let (name, inline_value) = match raw.split_once('=') {
+                    Some((name, value)) => (name, Some(value)),
+                    None => (raw, None),
+                };
+
+                let arg = self
+                    .args
+                    .iter()
+                    .find(|a| !a.is_positional() && a.name == name)
+                    .ok_or_else(|| Error::UnknownArgument(self.name, name.to_string()))?;
+
+                let value = match (arg.value_name, inline_value) {
+                    (Some(_), Some(value)) => value.to_string(),
+                    (Some(_), None) => args
+                        .next()
+                        .ok_or(Error::MissingValue(arg.name))?
+                        .to_string(),
+                    (None, Some(_)) => return Err(Error::UnexpectedValue(arg.name)),
+                    (None, None) => String::new(),
+                };
+
+                if matches.values.insert(arg.name, value).is_some() {
+                    return Err(Error::Duplicate(arg.name));
+                }
+            } else {
+                let arg = positionals
+                    .next()
+                    .ok_or_else(|| Error::UnknownArgument(self.name, raw.to_string()))?;
+                matches.values.insert(arg.name, raw.to_string());
+            }
+        }
+
+        if let Some(missing) = positionals.find(|a| a.required) {
+            return Err(Error::MissingArgument(missing.name));
+        }
+
+        Ok(matches)
+    }
+
+    /// Renders the help text for this command.
+    pub fn help(&self) -> String {
+        let positionals: Vec<_> = self.args.iter().filter(|a| a.is_positional()).collect();
+        let options: Vec<_> = self.args.iter().filter(|a| !a.is_positional()).collect();
+
+        let mut usage = vec![format!("Usage: cargo {}", self.name)];
+        usage.extend(positionals.iter().map(|a| a.usage()));
+        if !options.is_empty() {
+            usage.push("[OPTIONS]".into());
+        }
+
+        let mut lines = vec![self.about.to_string(), String::new(), usage.join(" ")];
+
+        if !positionals.is_empty() {
+            lines.push(String::new());
+            lines.push("Arguments:".into());
+            lines.extend(format_rows(
+                positionals.iter().map(|a| (a.usage(), a.help)).collect(),
+            ));
+        }
+
+        let mut option_rows: Vec<_> = options.iter().map(|a| (a.usage(), a.help)).collect();
+        option_rows.push(("-h, --help".into(), "Print help"));
+
+        lines.push(String::new());
+        lines.push("Options:".into());
+        lines.extend(format_rows(option_rows));
+
+        lines.join("\n")
+    }
+}
+
+fn format_rows(rows: Vec<(String, &str)>) -> Vec<String> {
+    let width = rows.iter().map(|(usage, _)| usage.len()).max().unwrap_or(0);
+    rows.into_iter()
+        .map(|(usage, help)| format!("  {usage:width$}  {help}"))
+        .collect()
+}
+
+/// A set of subcommands.
+#[derive(Debug)]
+pub struct Program {
+    pub about: &'static str,
+    pub commands: &'static [Command],
+}
+
+impl Program {
+    /// Parses `<command> [args]`, dispatching to the matching [`Command`].
+    pub fn parse<S: AsRef<str>>(&self, args: &[S]) -> Result<(&Command, Matches<'_>), Error> {
+        let Some((name, rest)) = args.split_first() else {
+            return Err(Error::MissingCommand);
+        };
+
+        let name = name.as_ref();
+        if name == "--help" || name == "-h" || name == "help" {
+            let help = match rest.first().and_then(|n| self.find(n.as_ref())) {
+                Some(command) => command.help(),
+                None => self.help(),
+            };
+            return Err(Error::Help(help));
+        }
+
+        let command = self
+            .find(name)
+            .ok_or_else(|| Error::UnknownCommand(name.to_string()))?;
+
+        Ok((command, command.parse(rest)?))
+    }
+
+    fn find(&self, name: &str) -> Option<&Command> {
+        self.commands.iter().find(|c| c.name == name)
+    }
+
+    /// Renders an overview of all commands.
+    pub fn help(&self) -> String {
+        let mut lines = vec![
+            self.about.to_string(),
+            String::new(),
+            "Usage: cargo <COMMAND> [OPTIONS]".into(),
+            String::new(),
+            "Commands:".into(),
+        ];
+        lines.extend(format_rows(
+            self.commands
+                .iter()
+                .map(|c| (c.name.to_string(), c.about))
+                .collect(),
+        ));
+        lines.push(String::new());
+        lines.push("Run `cargo <COMMAND> --help` for the options of a command.".into());
+        lines.join("\n")
+    }
+}
+
+/// Values of the arguments that were passed, keyed by argument name.
+#[derive(Debug, Default)]
+pub struct Matches<'a> {
+    values: HashMap<&'a str, String>,
+}
+
+impl Matches<'_> {
+    /// Returns whether a flag was passed.
+    pub fn flag(&self, name: &str) -> bool {
+        self.values.contains_key(name)
+    }
+
+    /// Returns the raw value of an argument, if passed.
+    pub fn value(&self, name: &str) -> Option<&str> {
+        self.values.get(name).map(String::as_str)
+    }
+
+    /// Parses the value of an optional argument.
+    pub fn parse<T>(&self, name: &'static str) -> Result<Option<T>, Error>
+    where
+        T: FromStr,
+        T::Err: Display,
+    {
+        self.value(name)
+            .map(|v| {
+                v.parse().map_err(|e: T::Err| Error::InvalidValue {
+                    name,
+                    value: v.to_string(),
+                    reason: e.to_string(),
+                })
+            })
+            .transpose()
+    }
+
+    /// Parses the value of a required argument.
+    pub fn parse_required<T>(&self, name: &'static str) -> Result<T, Error>
+    where
+        T: FromStr,
+        T::Err: Display,
+    {
+        self.parse(name)?.ok_or(Error::MissingArgument(name))
+    }
+}
+
+/// An error which can be returned when parsing arguments.
+/// [`Error::Help`] is returned when `--help` was requested and contains the rendered help text.
+#[derive(Debug)]
+pub enum Error {
+    Help(String),
+    MissingCommand,
+    UnknownCommand(String),
+    UnknownArgument(&'static str, String),
+    MissingArgument(&'static str),
+    MissingValue(&'static str),
+    UnexpectedValue(&'static str),
+    Duplicate(&'static str),
+    InvalidValue {
+        name: &'static str,
+        value: String,
+        reason: String,
+    },
+}
+
+impl std::error::Error for Error {}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::Help(help) => write!(f, "{help}"),
+            Error::MissingCommand => write!(
+                f,
+                "no command specified. Run with `--help` for a list of commands."
+            ),
+            Error::UnknownCommand(name) => write!(
+                f,
+                "unknown command `{name}`. Run with `--help` for a list of commands."
+            ),
+            Error::UnknownArgument(command, arg) => write!(
+                f,
+                "unexpected argument `{arg}`. Run `cargo {command} --help` for usage."
+            ),
+            Error::MissingArgument(name) => write!(f, "missing required argument `{name}`."),
+            Error::MissingValue(name) => write!(f, "`{name}` expects a value."),
+            Error::UnexpectedValue(name) => write!(f, "`{name}` does not take a value."),
+            Error::Duplicate(name) => write!(f, "`{name}` was specified more than once."),
+            Error::InvalidValue {
+                name,
+                value,
+                reason,
+            } => write!(f, "invalid value `{value}` for `{name}`: {reason}"),
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Arg, Command, Error, Program};
+    use crate::template::Day;
+
+    static PROGRAM: Program = Program {
+        about: "Advent of Code",
+        commands: &[Command {
+            name: "solve",
+            about: "Run a solution.",
+            args: &[
+                Arg::positional("DAY", "The day to run."),
+                Arg::flag("--release", "Run an optimized build."),
+                Arg::option("--submit", "PART", "Submit a part via aoc-cli."),
+            ],
+        }],
+    };
+
+    static SOLVE: &Command = &PROGRAM.commands[0];
+
+    #[test]
+    fn parses_flags_options_and_positionals() {
+        let m = SOLVE.parse(&["3", "--release", "--submit", "2"]).unwrap();
+        assert_eq!(
+            m.parse_required::<Day>("DAY").unwrap(),
+            Day::new(3).unwrap()
+        );
+        assert!(m.flag("--release"));
+        assert_eq!(m.parse::<u8>("--submit").unwrap(), Some(2));
+    }
+
+    #[test]
+    fn parses_inline_values() {
+        let m = SOLVE.parse(&["3", "--submit=1"]).unwrap();
+        assert_eq!(m.value("--submit"), Some("1"));
+        assert!(!m.flag("--release"));
+    }
+
+    #[test]
+    fn rejects_unknown_arguments() {
+        assert!(matches!(
+            SOLVE.parse(&["3", "--relase"]),
+            Err(Error::UnknownArgument("solve", _))
+        ));
+        assert!(matches!(
+            SOLVE.parse(&["3", "4"]),
+            Err(Error::UnknownArgument("solve", _))
+        ));
+    }
+
+    #[test]
+    fn rejects_missing_arguments() {
+        assert!(matches!(
+            SOLVE.parse(&["--release"]),
+            Err(Error::MissingArgument("DAY"))
+        ));
+        assert!(matches!(
+            SOLVE.parse(&["3", "--submit"]),
+            Err(Error::MissingValue("--submit"))
+        ));
+    }
+
+    #[test]
+    fn rejects_invalid_values() {
+        let m = SOLVE.parse(&["26"]).unwrap();
+        assert!(matches!(
+            m.parse_required::<Day>("DAY"),
+            Err(Error::InvalidValue { name: "DAY", .. })
+        ));
+    }
+
+    #[test]
+    fn renders_help() {
+        let Err(Error::Help(help)) = SOLVE.parse(&["--help"]) else {
+            panic!("expected help");
+        };
+        assert!(help.contains("Usage: cargo solve <DAY> [OPTIONS]"));
+        assert!(help.contains("--submit <PART>"));
+    }
+
+    #[test]
+    fn dispatches_commands() {
+        let (command, _) = PROGRAM.parse(&["solve", "1"]).unwrap();
+        assert_eq!(command.name, "solve");
+        assert!(matches!(
+            PROGRAM.parse(&["solv"]),
+            Err(Error::UnknownCommand(_))
+        ));
+        assert!(matches!(PROGRAM.parse(&["help"]), Err(Error::Help(_))));
+    }
+}
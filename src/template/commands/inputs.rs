@@ -0,0 +1,161 @@
+use std::{collections::BTreeMap, fs, path::Path, process, str::FromStr};
+
+use crate::template::archive::{self, Manifest, ARCHIVE_PATH, MANIFEST_PATH};
+use crate::template::normalize;
+
+static INPUTS_DIR: &str = "./data/inputs";
+
+pub enum Action {
+    Pack,
+    Unpack,
+    Verify,
+}
+
+impl FromStr for Action {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "pack" => Ok(Action::Pack),
+            "unpack" => Ok(Action::Unpack),
+            "verify" => Ok(Action::Verify),
+            _ => Err("expecting one of `pack`, `unpack` or `verify`".into()),
+        }
+    }
+}
+
+pub fn handle(action: Action) {
+    match action {
+        Action::Pack => pack(),
+        Action::Unpack => unpack(),
+        Action::Verify => verify(),
+    }
+}
+
+/// Collects all non-empty `.txt` files in the inputs folder.
+fn read_plain_inputs() -> BTreeMap<String, String> {
+    let entries = match fs::read_dir(INPUTS_DIR) {
+        Ok(entries) => entries,
+        Err(e) => {
+            eprintln!("Failed to read \"{INPUTS_DIR}\": {e}");
+            process::exit(1);
+        }
+    };
+
+    entries
+        .filter_map(Result::ok)
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "txt"))
+        .filter_map(|path| {
+            let name = path.file_name()?.to_string_lossy().to_string();
+            let input = normalize(&fs::read_to_string(&path).ok()?);
+            (!input.is_empty()).then_some((name, input))
+        })
+        .collect()
+}
+
+fn pack() {
+    let inputs = read_plain_inputs();
+
+    if inputs.is_empty() {
+        eprintln!(
+            "No inputs found in \"{INPUTS_DIR}\". Download some with `cargo download <day>`."
+        );
+        process::exit(1);
+    }
+
+    if let Err(e) = archive::write_archive(&inputs) {
+        eprintln!("Failed to write input archive: {e}");
+        process::exit(1);
+    }
+
+    if let Err(e) = Manifest::from_inputs(&inputs).store_file() {
+        eprintln!("Failed to write checksum manifest: {e}");
+        process::exit(1);
+    }
+
+    println!("🎄 Packed {} inputs into \"{ARCHIVE_PATH}\".", inputs.len());
+    println!("🎄 Wrote checksums to \"{MANIFEST_PATH}\".");
+}
+
+fn unpack() {
+    let inputs = match archive::read_archive() {
+        Ok(inputs) => inputs,
+        Err(e) => {
+            eprintln!("Failed to read input archive: {e}");
+            process::exit(1);
+        }
+    };
+
+    let manifest = Manifest::read_from_file().unwrap_or_default();
+    let mut has_stale_entries = false;
+    let mut unpacked = 0;
+
+    for (name, input) in &inputs {
+        if manifest.verify(name, input) == Some(false) {
+            eprintln!("Archived input \"{name}\" does not match its checksum, skipping.");
+            has_stale_entries = true;
+            continue;
+        }
+
+        let path = Path::new(INPUTS_DIR).join(name);
+        if let Err(e) = fs::write(&path, input) {
+            eprintln!("Failed to write \"{}\": {e}", path.display());
+            process::exit(1);
+        }
+        unpacked += 1;
+    }
+
+    println!("🎄 Unpacked {unpacked} inputs into \"{INPUTS_DIR}\".");
+
+    if has_stale_entries {
+        eprintln!("The archive is out of date with \"{MANIFEST_PATH}\". Run `cargo inputs pack` to update it.");
+        process::exit(1);
+    }
+}
+
+fn verify() {
+    let Some(manifest) = Manifest::read_from_file() else {
+        eprintln!("No checksum manifest found at \"{MANIFEST_PATH}\". Run `cargo inputs pack` to create it.");
+        process::exit(1);
+    };
+
+    let mut plain_inputs = read_plain_inputs();
+    let mut archived_inputs = match archive::read_archive() {
+        Ok(inputs) => inputs,
+        Err(archive::Error::IO(_)) => BTreeMap::new(),
+        Err(e) => {
+            eprintln!("Failed to read input archive: {e}");
+            process::exit(1);
+        }
+    };
+    let mut failures = 0;
+
+    for name in manifest.entries.keys() {
+        let input = plain_inputs
+            .remove(name)
+            .or_else(|| archived_inputs.remove(name));
+
+        match input.and_then(|input| manifest.verify(name, &input)) {
+            Some(true) => println!("{name}: ok"),
+            Some(false) => {
+                println!("{name}: checksum mismatch");
+                failures += 1;
+            }
+            None => {
+                println!("{name}: missing");
+                failures += 1;
+            }
+        }
+    }
+
+    for name in plain_inputs.keys() {
+        println!("{name}: not in manifest");
+    }
+
+    if failures > 0 {
+        eprintln!("---");
+        eprintln!("{failures} input(s) are stale, corrupted or missing.");
+        process::exit(1);
+    }
+}
@@ -1,6 +1,12 @@
 pub mod all;
 pub mod download;
+#[cfg(feature = "encrypted-inputs")]
+pub mod inputs;
 pub mod read;
+pub mod readme;
 pub mod scaffold;
 pub mod solve;
+pub mod stars;
 pub mod time;
+pub mod verify;
+pub mod watch_day;
@@ -0,0 +1,73 @@
+use std::process;
+
+use crate::template::answers::Answers;
+use crate::template::readme::{self, Section};
+use crate::template::readme_benchmarks::Benchmarks;
+use crate::template::readme_stars::{self, Stars};
+use crate::template::readme_summary::{DaySummary, SlowestDays};
+use crate::template::timings::Timings;
+use crate::template::{aoc_cli, ANSI_BOLD, ANSI_RESET};
+
+static SLOWEST_DAYS_LIMIT: usize = 5;
+
+/// Renders every section that has markers in the README from the stored timings and answer ledger.
+/// Sections without data are skipped, so an empty ledger does not wipe an existing table.
+pub fn handle(check: bool) {
+    let content = match readme::read() {
+        Ok(content) => content,
+        Err(e) => {
+            eprintln!("Failed to read README: {e}");
+            process::exit(1);
+        }
+    };
+
+    let timings = Timings::read_from_file();
+    let stars = readme_stars::from_answers(&Answers::read_from_file());
+
+    let benchmarks = Benchmarks(timings.clone());
+    let star_table = aoc_cli::get_year().map(|year| Stars {
+        year,
+        days: stars.clone(),
+    });
+    let summary = DaySummary {
+        stars: &stars,
+        timings: &timings,
+    };
+    let slowest = SlowestDays {
+        timings: &timings,
+        limit: SLOWEST_DAYS_LIMIT,
+    };
+
+    let mut sections: Vec<&dyn Section> = vec![];
+    if !timings.data.is_empty() {
+        sections.extend([&benchmarks as &dyn Section, &slowest]);
+    }
+    if !stars.is_empty() {
+        sections.extend(star_table.as_ref().map(|s| s as &dyn Section));
+    }
+    if !timings.data.is_empty() || !stars.is_empty() {
+        sections.push(&summary);
+    }
+    sections.retain(|section| readme::has_section(&content, section.name()));
+
+    if sections.is_empty() {
+        println!("No README sections to update.");
+        return;
+    }
+
+    match readme::update(&sections, check) {
+        Ok(()) => {
+            let verb = if check { "Checked" } else { "Updated" };
+            for section in &sections {
+                println!("{verb} {ANSI_BOLD}{}{ANSI_RESET}", section.name());
+            }
+        }
+        Err(e) => {
+            eprintln!("{e}");
+            if check {
+                eprintln!("Run `cargo readme` to update them.");
+            }
+            process::exit(1);
+        }
+    }
+}
@@ -1,27 +1,25 @@
 use std::process::{Command, Stdio};
 
+use crate::template::run_multi::BuildProfile;
+use crate::template::runner::RunOptions;
 use crate::template::Day;
 
-pub fn handle(day: Day, release: bool, dhat: bool, submit_part: Option<u8>) {
+pub fn handle(day: Day, release: bool, dhat: bool, allocs: bool, options: &RunOptions) {
     let mut cmd_args = vec!["run".to_string(), "--bin".to_string(), day.to_string()];
 
-    if dhat {
-        cmd_args.extend([
-            "--profile".to_string(),
-            "dhat".to_string(),
-            "--features".to_string(),
-            "dhat-heap".to_string(),
-        ]);
-    } else if release {
-        cmd_args.push("--release".to_string());
-    }
+    let profile = if dhat {
+        BuildProfile::Dhat
+    } else if allocs {
+        BuildProfile::CountAllocs
+    } else if options.profile_part.is_some() {
+        BuildProfile::Profiling
+    } else {
+        BuildProfile::from_release(release)
+    };
+    cmd_args.extend(profile.cargo_args());
 
     cmd_args.push("--".to_string());
-
-    if let Some(submit_part) = submit_part {
-        cmd_args.push("--submit".to_string());
-        cmd_args.push(submit_part.to_string());
-    }
+    cmd_args.extend(options.to_args());
 
     let mut cmd = Command::new("cargo")
         .args(&cmd_args)
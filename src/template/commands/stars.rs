@@ -0,0 +1,101 @@
+use std::process;
+
+use crate::template::answers::Answers;
+use crate::template::run_multi::{run_multi, BuildProfile};
+use crate::template::{aoc_cli, readme_stars, DaySet};
+
+/// Rewrites the stars table in the README. Completion is taken from the answer ledger.
+/// With `run`, all solutions are executed and only parts which reproduce their ledger
+/// answer are counted.
+pub fn handle(run: bool) {
+    let Some(year) = aoc_cli::get_year() else {
+        eprintln!("Could not determine the year, please set `AOC_YEAR` in `.cargo/config.toml`.");
+        process::exit(1);
+    };
+
+    let ledger = Answers::read_from_file();
+
+    let answers = if run {
+        let results = run_multi(&DaySet::all(), BuildProfile::Release, false, None);
+        verified(&ledger, results.answers)
+    } else {
+        ledger
+    };
+
+    let stars = readme_stars::Stars {
+        year,
+        days: readme_stars::from_answers(&answers),
+    };
+
+    // keep the existing table rather than wiping it when nothing has been recorded yet.
+    if stars.days.is_empty() {
+        eprintln!("No answers found. Record some with `cargo verify --store` or pass `--run`.");
+        process::exit(1);
+    }
+
+    let count: usize = stars.days.iter().map(readme_stars::DayStars::count).sum();
+
+    println!();
+    match readme_stars::update(stars) {
+        Ok(()) => println!("⭐ Updated README with {count} stars."),
+        Err(e) => {
+            eprintln!("Failed to update stars: {e}");
+            process::exit(1);
+        }
+    }
+}
+
+/// Drops parts whose result does not match the ledger, including parts without a ledger entry.
+fn verified(ledger: &Answers, mut results: Answers) -> Answers {
+    for answer in &mut results.data {
+        let (part_1, part_2) = ledger
+            .get(answer.day)
+            .map_or((None, None), |e| (e.part_1.as_ref(), e.part_2.as_ref()));
+
+        if part_1.is_none() || part_1 != answer.part_1.as_ref() {
+            answer.part_1 = None;
+        }
+        if part_2.is_none() || part_2 != answer.part_2.as_ref() {
+            answer.part_2 = None;
+        }
+    }
+
+    results
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::verified;
+    use crate::day;
+    use crate::template::answers::{Answer, Answers};
+
+    #[test]
+    fn awards_stars_for_ledger_matches_only() {
+        let ledger = Answers {
+            data: vec![Answer {
+                day: day!(1),
+                part_1: Some("11".into()),
+                part_2: None,
+            }],
+        };
+        let results = Answers {
+            data: vec![
+                Answer {
+                    day: day!(1),
+                    part_1: Some("11".into()),
+                    part_2: Some("31".into()),
+                },
+                Answer {
+                    day: day!(2),
+                    part_1: Some("2".into()),
+                    part_2: None,
+                },
+            ],
+        };
+
+        let stars = verified(&ledger, results);
+        assert_eq!(stars.data[0].part_1.as_deref(), Some("11"));
+        assert_eq!(stars.data[0].part_2, None);
+        assert_eq!(stars.data[1].part_1, None);
+    }
+}
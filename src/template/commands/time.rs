@@ -1,30 +1,107 @@
-use std::collections::HashSet;
+use std::{fs, path::Path, process};
 
-use crate::template::run_multi::run_multi;
+use crate::template::run_multi::{run_multi, BuildProfile};
 use crate::template::timings::Timings;
-use crate::template::{all_days, readme_benchmarks, Day};
+use crate::template::{compare, readme_benchmarks, report, scaling, DaySet};
 
-pub fn handle(day: Option<Day>, run_all: bool, store: bool) {
+pub use crate::template::report::ReportFormat;
+pub use crate::template::scaling::ThreadCounts;
+
+pub struct TimeOptions {
+    pub days: Option<DaySet>,
+    pub run_all: bool,
+    pub store: bool,
+    pub slow_threshold: Option<f64>,
+    pub report: Option<ReportFormat>,
+    pub out: Option<String>,
+    pub compare: Option<String>,
+    pub dhat: bool,
+    pub allocs: bool,
+    pub perf: bool,
+    /// Rayon pool sizes to run with. Prints a scaling table if there is more than one.
+    pub threads: Option<ThreadCounts>,
+}
+
+pub fn handle(options: TimeOptions) {
     let stored_timings = Timings::read_from_file();
 
-    let days_to_run = day.map_or_else(
-        || {
-            if run_all {
-                all_days().collect()
-            } else {
-                // when the `--all` flag is not set, filter out days that are fully benched.
-                all_days()
-                    .filter(|day| !stored_timings.is_day_complete(*day))
-                    .collect()
+    let baseline = options.compare.as_deref().map(|source| {
+        compare::load_baseline(source).unwrap_or_else(|e| {
+            eprintln!("Failed to load baseline: {e}");
+            process::exit(1);
+        })
+    });
+
+    // when comparing without a selection, compare the stored timings instead of running.
+    if let Some(baseline) = &baseline {
+        if options.days.is_none() && !options.run_all {
+            compare::print(&compare::compare(baseline, &stored_timings));
+            return;
+        }
+    }
+
+    let days_to_run = options.days.unwrap_or_else(|| {
+        if options.run_all {
+            DaySet::all()
+        } else {
+            // when the `--all` flag is not set, filter out days that are fully benched.
+            DaySet::unsolved()
+        }
+    });
+
+    let profile = if options.dhat {
+        BuildProfile::Dhat
+    } else if options.allocs {
+        BuildProfile::CountAllocs
+    } else if options.perf {
+        BuildProfile::PerfCounters
+    } else {
+        BuildProfile::Release
+    };
+
+    let mut timings = match &options.threads {
+        Some(ThreadCounts(counts)) => {
+            let runs: Vec<(usize, Timings)> = counts
+                .iter()
+                .map(|&threads| {
+                    let timings = run_multi(&days_to_run, profile, true, Some(threads));
+                    (threads, timings.timings.unwrap())
+                })
+                .collect();
+
+            if runs.len() > 1 {
+                scaling::print(&scaling::scaling(&runs));
             }
-        },
-        |day| HashSet::from([day]),
-    );
 
-    let timings = run_multi(&days_to_run, true, true).unwrap();
+            scaling::fastest(runs)
+        }
+        None => run_multi(&days_to_run, profile, true, None)
+            .timings
+            .unwrap(),
+    };
+    timings.slow_threshold_millis = options.slow_threshold;
+    // durations are skewed by allocation tracking, only keep the allocation stats.
+    let merged_timings = if profile.is_profiling() {
+        stored_timings.merge_allocations(&timings)
+    } else {
+        stored_timings.merge(&timings)
+    };
+
+    if let Some(baseline) = &baseline {
+        compare::print(&compare::compare(baseline, &timings));
+    }
+
+    // infer the report format from the file extension if only `--out` is given.
+    let format = options.report.or_else(|| {
+        let extension = Path::new(options.out.as_deref()?).extension()?;
+        extension.to_str()?.parse().ok()
+    });
 
-    if store {
-        let merged_timings = stored_timings.merge(&timings);
+    if let Some(format) = format {
+        write_report(format, options.out.as_deref(), &merged_timings);
+    }
+
+    if options.store {
         merged_timings.store_file().unwrap();
 
         println!();
@@ -38,3 +115,23 @@ pub fn handle(day: Option<Day>, run_all: bool, store: bool) {
         }
     }
 }
+
+/// Writes a report of all known timings to `out`, or to stdout if not set.
+fn write_report(format: ReportFormat, out: Option<&str>, timings: &Timings) {
+    let report = report::render(format, timings);
+
+    match out {
+        Some(path) => {
+            if let Err(e) = fs::write(path, report) {
+                eprintln!("Failed to write report to \"{path}\": {e}");
+                process::exit(1);
+            }
+            println!();
+            println!("Wrote report to \"{path}\".");
+        }
+        None => {
+            println!();
+            print!("{report}");
+        }
+    }
+}
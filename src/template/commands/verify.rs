@@ -0,0 +1,52 @@
+use std::process;
+
+use crate::template::answers::Answers;
+use crate::template::run_multi::{run_multi, BuildProfile};
+use crate::template::{DaySet, ANSI_BOLD, ANSI_RESET};
+
+pub fn handle(days: &DaySet, is_release: bool, store: bool) {
+    let ledger = Answers::read_from_file();
+    let results = run_multi(days, BuildProfile::from_release(is_release), false, None);
+
+    println!();
+    println!("{ANSI_BOLD}Verification{ANSI_RESET}");
+    println!("------");
+
+    let mut failures = 0;
+
+    for answer in &results.answers.data {
+        for part in [1, 2] {
+            let expected = ledger.get(answer.day).and_then(|a| a.part(part));
+            let prefix = format!("Day {} Part {part}:", answer.day);
+
+            match (answer.part(part), expected) {
+                (Some(actual), Some(expected)) if actual == expected => {
+                    println!("{prefix} ✔ {actual}");
+                }
+                (Some(actual), Some(expected)) => {
+                    println!("{prefix} ✖ got {actual}, expected {expected}");
+                    failures += 1;
+                }
+                (None, Some(expected)) => {
+                    println!("{prefix} ✖ no result, expected {expected}");
+                    failures += 1;
+                }
+                (Some(actual), None) => println!("{prefix} ? {actual} (not in ledger)"),
+                (None, None) => {}
+            }
+        }
+    }
+
+    if store {
+        match ledger.merge(&results.answers).store_file() {
+            Ok(()) => println!("\nStored answers in ledger."),
+            Err(e) => {
+                eprintln!("Failed to store answers: {e}");
+                process::exit(1);
+            }
+        }
+    } else if failures > 0 {
+        eprintln!("\n{failures} part(s) did not match the answer ledger.");
+        process::exit(1);
+    }
+}
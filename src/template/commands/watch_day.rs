@@ -0,0 +1,168 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::PathBuf;
+use std::process::{self, Command, Stdio};
+use std::thread;
+use std::time::{Duration, SystemTime};
+
+use crate::template::answers::Answer;
+use crate::template::run_multi::{child_commands, get_path_for_bin, BuildProfile};
+use crate::template::{Day, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
+
+static POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+pub fn handle(day: Day, release: bool) {
+    if fs::metadata(get_path_for_bin(day)).is_err() {
+        eprintln!("Day {day} has not been scaffolded yet. Run `cargo scaffold {day}` first.");
+        process::exit(1);
+    }
+
+    println!("{ANSI_ITALIC}Watching day {day} for changes, press Ctrl-C to stop.{ANSI_RESET}");
+
+    let mut snapshot = modified_times(day);
+    let mut previous = run(day, release, None);
+
+    loop {
+        thread::sleep(POLL_INTERVAL);
+
+        let next = modified_times(day);
+        if next != snapshot {
+            snapshot = next;
+            previous = run(day, release, previous);
+        }
+    }
+}
+
+/// Files that trigger a re-run: the solution, the shared library and the day's examples.
+fn watched_paths(day: Day) -> Vec<PathBuf> {
+    let mut paths = vec![PathBuf::from(get_path_for_bin(day)), "./src/lib.rs".into()];
+
+    if let Ok(entries) = fs::read_dir("./data/examples") {
+        paths.extend(
+            entries
+                .filter_map(Result::ok)
+                .map(|entry| entry.path())
+                .filter(|path| {
+                    path.file_name().is_some_and(|name| {
+                        let name = name.to_string_lossy();
+                        name.starts_with(&day.to_string()) && name.ends_with(".txt")
+                    })
+                }),
+        );
+    }
+
+    paths
+}
+
+/// Modification times of all watched files. Added or removed files change the snapshot as well.
+fn modified_times(day: Day) -> BTreeMap<PathBuf, SystemTime> {
+    watched_paths(day)
+        .into_iter()
+        .filter_map(|path| {
+            let modified = fs::metadata(&path).and_then(|m| m.modified()).ok()?;
+            Some((path, modified))
+        })
+        .collect()
+}
+
+/// Runs the example tests, and the real input if they pass. Returns the latest known answers.
+fn run(day: Day, release: bool, previous: Option<Answer>) -> Option<Answer> {
+    println!();
+    println!("{ANSI_BOLD}Day {day}: examples{ANSI_RESET}");
+    println!("------");
+
+    let mut args = vec!["test", "--quiet", "--bin"];
+    let day_str = day.to_string();
+    args.push(&day_str);
+    if release {
+        args.push("--release");
+    }
+
+    let tests_passed = Command::new("cargo")
+        .args(&args)
+        .stdout(Stdio::inherit())
+        .stderr(Stdio::inherit())
+        .status()
+        .is_ok_and(|status| status.success());
+
+    if !tests_passed {
+        println!("{ANSI_ITALIC}Example tests failed, skipping real input.{ANSI_RESET}");
+        return previous;
+    }
+
+    println!();
+    println!("{ANSI_BOLD}Day {day}: input{ANSI_RESET}");
+    println!("------");
+
+    let output =
+        match child_commands::run_solution(day, false, BuildProfile::from_release(release), None) {
+            Ok(output) => output,
+            Err(e) => {
+                eprintln!("Failed to run solution: {e:?}");
+                return previous;
+            }
+        };
+
+    let answer = child_commands::parse_answers(&output, day);
+    let changes = diff_answers(previous.as_ref(), &answer);
+
+    if !changes.is_empty() {
+        println!();
+        changes.iter().for_each(|line| println!("{line}"));
+    }
+
+    Some(answer)
+}
+
+/// Describes the answers that changed compared to the previous run, one line per part.
+fn diff_answers(previous: Option<&Answer>, next: &Answer) -> Vec<String> {
+    let Some(previous) = previous else {
+        return vec![];
+    };
+
+    [1, 2]
+        .into_iter()
+        .filter_map(|part| {
+            let (old, new) = (previous.part(part), next.part(part));
+            (old != new).then(|| {
+                format!(
+                    "Part {part}: {} → {ANSI_BOLD}{}{ANSI_RESET}",
+                    old.unwrap_or("✖"),
+                    new.unwrap_or("✖")
+                )
+            })
+        })
+        .collect()
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::diff_answers;
+    use crate::{
+        day,
+        template::{answers::Answer, ANSI_BOLD, ANSI_RESET},
+    };
+
+    fn answer(part_1: Option<&str>, part_2: Option<&str>) -> Answer {
+        Answer {
+            day: day!(1),
+            part_1: part_1.map(String::from),
+            part_2: part_2.map(String::from),
+        }
+    }
+
+    #[test]
+    fn diffs_changed_parts() {
+        let previous = answer(Some("11"), None);
+        let next = answer(Some("11"), Some("31"));
+        assert_eq!(
+            diff_answers(Some(&previous), &next),
+            vec![format!("Part 2: ✖ → {ANSI_BOLD}31{ANSI_RESET}")]
+        );
+    }
+
+    #[test]
+    fn skips_diff_for_first_run() {
+        assert!(diff_answers(None, &answer(Some("11"), None)).is_empty());
+    }
+}
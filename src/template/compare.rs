@@ -0,0 +1,212 @@
+/// Module that compares benchmark timings against a baseline.
+use std::{fs, path::Path, process::Command};
+
+use crate::template::timings::{Timing, Timings, TIMINGS_FILE_PATH};
+use crate::template::{Day, ANSI_BOLD, ANSI_RESET};
+
+static ANSI_RED: &str = "\x1b[31m";
+static ANSI_GREEN: &str = "\x1b[32m";
+
+/// Loads baseline timings from a file, or from `data/timings.json` at a git revision.
+pub fn load_baseline(source: &str) -> Result<Timings, String> {
+    let json = if Path::new(source).is_file() {
+        fs::read_to_string(source).map_err(|e| format!("Failed to read \"{source}\": {e}"))?
+    } else {
+        let path = TIMINGS_FILE_PATH.trim_start_matches("./");
+        let output = Command::new("git")
+            .args(["show", &format!("{source}:{path}")])
+            .output()
+            .map_err(|e| format!("Failed to run git: {e}"))?;
+
+        if !output.status.success() {
+            return Err(format!(
+                "`{source}` is neither a timings file nor a git revision with \"{path}\"."
+            ));
+        }
+
+        String::from_utf8_lossy(&output.stdout).to_string()
+    };
+
+    Timings::try_from(json)
+}
+
+/// Change of a single part between the baseline and the current timings.
+#[derive(Debug, PartialEq)]
+pub struct PartDelta {
+    pub day: Day,
+    pub part: u8,
+    pub baseline_nanos: f64,
+    pub current_nanos: f64,
+    /// `None` if the distribution of either side is unknown.
+    pub is_significant: Option<bool>,
+}
+
+impl PartDelta {
+    pub fn ratio(&self) -> f64 {
+        self.current_nanos / self.baseline_nanos
+    }
+}
+
+/// Pairs up the parts present in both timings.
+pub fn compare(baseline: &Timings, current: &Timings) -> Vec<PartDelta> {
+    current
+        .data
+        .iter()
+        .filter_map(|timing| Some((baseline.get(timing.day)?, timing)))
+        .flat_map(|(baseline, current)| {
+            [1, 2].into_iter().filter_map(move |part| {
+                Some(PartDelta {
+                    day: current.day,
+                    part,
+                    baseline_nanos: baseline.part_nanos(part)?,
+                    current_nanos: current.part_nanos(part)?,
+                    is_significant: is_significant(baseline, current, part),
+                })
+            })
+        })
+        .collect()
+}
+
+/// Welch's t-test at a 95% confidence level.
+#[allow(clippy::cast_precision_loss)]
+fn is_significant(baseline: &Timing, current: &Timing, part: u8) -> Option<bool> {
+    let sample = |timing: &Timing| -> Option<(f64, f64, f64)> {
+        let metrics = timing.metrics(part)?;
+        let samples = metrics.samples.filter(|n| *n > 1)? as f64;
+        Some((timing.part_nanos(part)?, metrics.stddev_nanos?, samples))
+    };
+
+    let (mean_a, stddev_a, n_a) = sample(baseline)?;
+    let (mean_b, stddev_b, n_b) = sample(current)?;
+
+    let var_a = stddev_a.powi(2) / n_a;
+    let var_b = stddev_b.powi(2) / n_b;
+
+    if var_a + var_b == 0.0 {
+        return Some(mean_a != mean_b);
+    }
+
+    let t = (mean_a - mean_b).abs() / (var_a + var_b).sqrt();
+    let df = (var_a + var_b).powi(2) / (var_a.powi(2) / (n_a - 1.0) + var_b.powi(2) / (n_b - 1.0));
+
+    Some(t > critical_t(df))
+}
+
+/// Two-sided critical value of Student's t-distribution for p = 0.05.
+fn critical_t(df: f64) -> f64 {
+    static TABLE: [f64; 10] = [
+        12.706, 4.303, 3.182, 2.776, 2.571, 2.447, 2.365, 2.306, 2.262, 2.228,
+    ];
+
+    match df {
+        df if df < 1.0 => TABLE[0],
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        df if df <= 10.0 => TABLE[df as usize - 1],
+        df if df <= 30.0 => 2.042 + (30.0 - df) * 0.009,
+        _ => 1.96,
+    }
+}
+
+pub fn format_nanos(nanos: f64) -> String {
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    let duration = std::time::Duration::from_nanos(nanos as u64);
+    format!("{duration:.1?}")
+}
+
+pub fn print(deltas: &[PartDelta]) {
+    println!();
+    println!("{ANSI_BOLD}Comparison{ANSI_RESET}");
+    println!("------");
+
+    if deltas.is_empty() {
+        println!("No parts in common with the baseline.");
+        return;
+    }
+
+    for delta in deltas {
+        let ratio = delta.ratio();
+        let color = match delta.is_significant {
+            Some(false) => "",
+            _ if ratio < 1.0 => ANSI_GREEN,
+            _ if ratio > 1.0 => ANSI_RED,
+            _ => "",
+        };
+        let significance = match delta.is_significant {
+            Some(true) => " (significant)",
+            Some(false) => " (noise)",
+            None => "",
+        };
+
+        println!(
+            "Day {} Part {}: {} → {} {color}{:+.1}% ({ratio:.2}x){ANSI_RESET}{significance}",
+            delta.day,
+            delta.part,
+            format_nanos(delta.baseline_nanos),
+            format_nanos(delta.current_nanos),
+            (ratio - 1.0) * 100.0
+        );
+    }
+
+    let baseline: f64 = deltas.iter().map(|d| d.baseline_nanos).sum();
+    let current: f64 = deltas.iter().map(|d| d.current_nanos).sum();
+    println!(
+        "\n{ANSI_BOLD}Total:{ANSI_RESET} {} → {} ({:.2}x)",
+        format_nanos(baseline),
+        format_nanos(current),
+        current / baseline
+    );
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::compare;
+    use crate::{
+        day,
+        template::timings::{PartMetrics, Timing, Timings},
+    };
+
+    fn timing(part_1: &str, part_2: Option<&str>, stddev_nanos: Option<f64>) -> Timings {
+        let metrics = PartMetrics {
+            stddev_nanos,
+            samples: Some(100),
+            ..Default::default()
+        };
+        Timings {
+            data: vec![Timing {
+                day: day!(1),
+                part_1: Some(part_1.into()),
+                part_2: part_2.map(String::from),
+                total_nanos: 0.0,
+                threads: None,
+                metrics: [metrics; 2],
+            }],
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn compares_common_parts() {
+        let deltas = compare(
+            &timing("10ms", Some("1ms"), None),
+            &timing("5ms", None, None),
+        );
+        assert_eq!(deltas.len(), 1);
+        assert_eq!(deltas[0].ratio(), 0.5);
+        assert_eq!(deltas[0].is_significant, None);
+    }
+
+    #[test]
+    fn checks_significance() {
+        let noisy = compare(
+            &timing("10.0ms", None, Some(2e6)),
+            &timing("10.1ms", None, Some(2e6)),
+        );
+        assert_eq!(noisy[0].is_significant, Some(false));
+
+        let stable = compare(
+            &timing("10.0ms", None, Some(1e4)),
+            &timing("9.0ms", None, Some(1e4)),
+        );
+        assert_eq!(stable[0].is_significant, Some(true));
+    }
+}
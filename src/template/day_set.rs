@@ -0,0 +1,194 @@
+use std::error::Error;
+use std::fmt::Display;
+use std::fs;
+use std::str::FromStr;
+use std::time::SystemTime;
+
+use crate::template::run_multi::get_path_for_bin;
+use crate::template::timings::{Timings, TIMINGS_FILE_PATH};
+use crate::template::{all_days, Day};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Selector {
+    Range(Day, Day),
+    Unsolved,
+    Slow,
+    Changed,
+}
+
+/// A selection of days, parsed from a comma-separated list of expressions:
+///  - a day (`7`) or a range of days (`1-10`).
+///  - `unsolved`: days without stored timings for both parts.
+///  - `slow`: days with a stored total time above the slow threshold.
+///  - `changed`: days whose binary was modified since timings were last stored.
+///
+/// The selection is resolved to concrete days with [`DaySet::resolve`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DaySet(Vec<Selector>);
+
+impl DaySet {
+    /// Every day of advent.
+    pub fn all() -> Self {
+        Self(vec![Selector::Range(crate::day!(1), crate::day!(25))])
+    }
+
+    /// Days without stored timings for both parts.
+    pub fn unsolved() -> Self {
+        Self(vec![Selector::Unsolved])
+    }
+
+    /// Resolves the selection to a sorted list of days, reading stored timings if needed.
+    pub fn resolve(&self) -> Vec<Day> {
+        let needs_timings = self.0.iter().any(|s| !matches!(s, Selector::Range(..)));
+        let timings = if needs_timings {
+            Timings::read_from_file()
+        } else {
+            Timings::default()
+        };
+
+        let timings_modified = fs::metadata(TIMINGS_FILE_PATH)
+            .and_then(|m| m.modified())
+            .ok();
+
+        self.resolve_with(&timings, |day| {
+            is_bin_modified_since(day, &timings, timings_modified)
+        })
+    }
+
+    fn resolve_with(&self, timings: &Timings, is_changed: impl Fn(Day) -> bool) -> Vec<Day> {
+        all_days()
+            .filter(|day| {
+                self.0.iter().any(|selector| match selector {
+                    Selector::Range(start, end) => day >= start && day <= end,
+                    Selector::Unsolved => !timings.is_day_complete(*day),
+                    Selector::Slow => timings.is_day_slow(*day),
+                    Selector::Changed => is_changed(*day),
+                })
+            })
+            .collect()
+    }
+}
+
+fn is_bin_modified_since(day: Day, timings: &Timings, since: Option<SystemTime>) -> bool {
+    let Ok(modified) = fs::metadata(get_path_for_bin(day)).and_then(|m| m.modified()) else {
+        // days that have not been scaffolded yet did not change.
+        return false;
+    };
+
+    match since {
+        Some(since) if timings.get(day).is_some() => modified > since,
+        _ => true,
+    }
+}
+
+impl FromStr for DaySet {
+    type Err = DaySetFromStrError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        s.split(',')
+            .map(str::trim)
+            .map(|part| {
+                let err = || DaySetFromStrError(part.to_string());
+                match part {
+                    "unsolved" => Ok(Selector::Unsolved),
+                    "slow" => Ok(Selector::Slow),
+                    "changed" => Ok(Selector::Changed),
+                    _ => {
+                        let (start, end) = part.split_once('-').unwrap_or((part, part));
+                        let start = Day::from_str(start.trim()).map_err(|_| err())?;
+                        let end = Day::from_str(end.trim()).map_err(|_| err())?;
+                        if start > end {
+                            return Err(err());
+                        }
+                        Ok(Selector::Range(start, end))
+                    }
+                }
+            })
+            .collect::<Result<_, _>>()
+            .map(Self)
+    }
+}
+
+/// An error which can be returned when parsing a [`DaySet`].
+#[derive(Debug)]
+pub struct DaySetFromStrError(String);
+
+impl Error for DaySetFromStrError {}
+
+impl Display for DaySetFromStrError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "`{}` is not a day, range or selector. Expecting a comma-separated list like `1-5,9` or `unsolved,changed`.",
+            self.0
+        )
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::DaySet;
+    use crate::{
+        day,
+        template::timings::{Timing, Timings},
+    };
+
+    fn get_mock_timings() -> Timings {
+        Timings {
+            data: vec![
+                Timing {
+                    day: day!(1),
+                    part_1: Some("10ms".into()),
+                    part_2: Some("200ms".into()),
+                    total_nanos: 2.1e+8,
+                    threads: None,
+                    metrics: Default::default(),
+                },
+                Timing {
+                    day: day!(2),
+                    part_1: Some("30ms".into()),
+                    part_2: None,
+                    total_nanos: 3e+7,
+                    threads: None,
+                    metrics: Default::default(),
+                },
+            ],
+            ..Default::default()
+        }
+    }
+
+    fn resolve(s: &str) -> Vec<u8> {
+        s.parse::<DaySet>()
+            .unwrap()
+            .resolve_with(&get_mock_timings(), |day| day == day!(5))
+            .into_iter()
+            .map(|day| day.into_inner())
+            .collect()
+    }
+
+    #[test]
+    fn resolves_days_and_ranges() {
+        assert_eq!(resolve("1-5,9, 3"), vec![1, 2, 3, 4, 5, 9]);
+        assert_eq!(resolve("07"), vec![7]);
+    }
+
+    #[test]
+    fn resolves_selectors() {
+        assert_eq!(resolve("slow"), vec![1]);
+        assert_eq!(resolve("changed"), vec![5]);
+        assert_eq!(resolve("unsolved").len(), 24);
+        assert_eq!(resolve("unsolved")[0], 2);
+        assert_eq!(resolve("slow,changed,24"), vec![1, 5, 24]);
+    }
+
+    #[test]
+    fn rejects_invalid_expressions() {
+        assert!("".parse::<DaySet>().is_err());
+        assert!("5-1".parse::<DaySet>().is_err());
+        assert!("1-26".parse::<DaySet>().is_err());
+        assert!("1,,2".parse::<DaySet>().is_err());
+        assert!("fast".parse::<DaySet>().is_err());
+    }
+}
@@ -0,0 +1,292 @@
+/// Reading, normalizing and validating puzzle input files.
+use std::{
+    env,
+    fmt::Display,
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use crate::template::{
+    archive::{self, Manifest},
+    Day,
+};
+
+/// Content that aoc-cli writes instead of the input when the session cookie is missing or expired.
+static PLACEHOLDER_MARKERS: [&str; 2] = [
+    "Puzzle inputs differ by user.",
+    "Please don't repeatedly request this endpoint",
+];
+
+/// An error which can be returned when reading an input file.
+#[derive(Debug)]
+pub enum InputError {
+    IO(PathBuf, io::Error),
+    Empty(PathBuf, Day),
+    Placeholder(PathBuf, Day),
+    ChecksumMismatch(PathBuf, Day),
+    Archive(String),
+    UnequalLineLengths {
+        line: usize,
+        expected: usize,
+        found: usize,
+    },
+    NonAscii {
+        line: usize,
+    },
+}
+
+impl std::error::Error for InputError {}
+
+impl Display for InputError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            InputError::IO(path, e) => {
+                write!(f, "could not open input file \"{}\": {e}", path.display())
+            }
+            InputError::Empty(path, day) => {
+                write!(f, "input file \"{}\" is empty. ", path.display())?;
+                write_hint(f, path, *day)
+            }
+            InputError::Placeholder(path, day) => {
+                write!(
+                    f,
+                    "input file \"{}\" contains a placeholder instead of puzzle input. ",
+                    path.display()
+                )?;
+                write_hint(f, path, *day)
+            }
+            InputError::ChecksumMismatch(path, day) => write!(
+                f,
+                "input file \"{}\" does not match its checksum in \"{}\", it is stale or corrupted. \
+                Try running `cargo inputs unpack` or `cargo download {day}`.",
+                path.display(),
+                archive::MANIFEST_PATH
+            ),
+            InputError::Archive(e) => write!(f, "could not read input archive: {e}"),
+            InputError::UnequalLineLengths {
+                line,
+                expected,
+                found,
+            } => write!(
+                f,
+                "expected all lines to have length {expected}, line {line} has length {found}."
+            ),
+            InputError::NonAscii { line } => {
+                write!(f, "expected input to be ASCII, line {line} is not.")
+            }
+        }
+    }
+}
+
+fn write_hint(f: &mut std::fmt::Formatter<'_>, path: &Path, day: Day) -> std::fmt::Result {
+    if path.components().any(|c| c.as_os_str() == "inputs") {
+        write!(f, "Try running `cargo download {day}` to fetch it.")
+    } else {
+        write!(f, "Paste the example from the puzzle description into it.")
+    }
+}
+
+/// Optional checks that can be applied to an input on top of the default validation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InputCheck {
+    /// All lines have the same length, e.g. for grid puzzles.
+    EqualLineLengths,
+    /// The input only contains ASCII characters, so it can be indexed byte-wise.
+    Ascii,
+}
+
+impl InputCheck {
+    /// Validates the (normalized) input against this check.
+    pub fn check(self, input: &str) -> Result<(), InputError> {
+        match self {
+            InputCheck::EqualLineLengths => {
+                let mut lines = input.lines();
+                let Some(first) = lines.next() else {
+                    return Ok(());
+                };
+                let expected = first.len();
+                match lines.enumerate().find(|(_, l)| l.len() != expected) {
+                    Some((i, l)) => Err(InputError::UnequalLineLengths {
+                        line: i + 2,
+                        expected,
+                        found: l.len(),
+                    }),
+                    None => Ok(()),
+                }
+            }
+            InputCheck::Ascii => match input.lines().position(|l| !l.is_ascii()) {
+                Some(i) => Err(InputError::NonAscii { line: i + 1 }),
+                None => Ok(()),
+            },
+        }
+    }
+}
+
+/// Normalizes raw file contents: strips a byte order mark, converts CRLF line endings
+/// to LF and collapses trailing blank lines into a single trailing newline.
+#[must_use]
+pub fn normalize(raw: &str) -> String {
+    let s = raw.strip_prefix('\u{feff}').unwrap_or(raw);
+    let mut s = s.replace("\r\n", "\n");
+    let trimmed_len = s.trim_end().len();
+    s.truncate(trimmed_len);
+    if !s.is_empty() {
+        s.push('\n');
+    }
+    s
+}
+
+fn get_data_path(folder: &str, file_name: &str) -> PathBuf {
+    let cwd = env::current_dir().unwrap();
+    cwd.join("data").join(folder).join(file_name)
+}
+
+/// Looks up an input in the encrypted archive, returns `None` if there is no archive or no entry for it.
+fn read_from_archive(file_name: &str) -> Option<Result<String, InputError>> {
+    match archive::read_archive() {
+        Ok(mut inputs) => inputs.remove(file_name).map(Ok),
+        Err(archive::Error::IO(_)) => None,
+        Err(e) => Some(Err(InputError::Archive(e.to_string()))),
+    }
+}
+
+fn read_normalized(folder: &str, file_name: &str, day: Day) -> Result<String, InputError> {
+    let filepath = get_data_path(folder, file_name);
+
+    let raw = match fs::read_to_string(&filepath) {
+        Ok(raw) => raw,
+        Err(e) => {
+            // fall back to the encrypted archive if the plain text input is not present.
+            let from_archive = (folder == "inputs" && e.kind() == io::ErrorKind::NotFound)
+                .then(|| read_from_archive(file_name))
+                .flatten();
+            from_archive.unwrap_or(Err(InputError::IO(filepath.clone(), e)))?
+        }
+    };
+
+    let input = normalize(&raw);
+
+    if input.is_empty() {
+        return Err(InputError::Empty(filepath, day));
+    }
+
+    if PLACEHOLDER_MARKERS.iter().any(|m| input.starts_with(m)) {
+        return Err(InputError::Placeholder(filepath, day));
+    }
+
+    if folder == "inputs" {
+        let is_stale = Manifest::read_from_file()
+            .and_then(|manifest| manifest.verify(file_name, &input))
+            .is_some_and(|is_valid| !is_valid);
+
+        if is_stale {
+            return Err(InputError::ChecksumMismatch(filepath, day));
+        }
+    }
+
+    Ok(input)
+}
+
+/// Reads a text file to a normalized string, returning an error if it is missing, empty or a placeholder.
+pub fn try_read_file(folder: &str, day: Day) -> Result<String, InputError> {
+    read_normalized(folder, &format!("{day}.txt"), day)
+}
+
+/// Like [`try_read_file`], but appends a part suffix. E.g. like `01-2.txt`.
+pub fn try_read_file_part(folder: &str, day: Day, part: u8) -> Result<String, InputError> {
+    read_normalized(folder, &format!("{day}-{part}.txt"), day)
+}
+
+/// Like [`try_read_file`], but additionally validates the input against a set of [`InputCheck`]s.
+pub fn try_read_file_checked(
+    folder: &str,
+    day: Day,
+    checks: &[InputCheck],
+) -> Result<String, InputError> {
+    let input = try_read_file(folder, day)?;
+    checks.iter().try_for_each(|c| c.check(&input))?;
+    Ok(input)
+}
+
+/// Helper function that reads a text file to a normalized string.
+///
+/// # Panics
+/// Panics with a descriptive message if the file is missing, empty or a placeholder.
+#[must_use]
+pub fn read_file(folder: &str, day: Day) -> String {
+    try_read_file(folder, day).unwrap_or_else(|e| panic!("{e}"))
+}
+
+/// Helper function that reads a text file to string, appending a part suffix. E.g. like `01-2.txt`.
+///
+/// # Panics
+/// Panics with a descriptive message if the file is missing, empty or a placeholder.
+#[must_use]
+pub fn read_file_part(folder: &str, day: Day, part: u8) -> String {
+    try_read_file_part(folder, day, part).unwrap_or_else(|e| panic!("{e}"))
+}
+
+/// Helper function that reads a text file to a normalized string and applies additional checks.
+///
+/// # Panics
+/// Panics with a descriptive message if the file can't be read or fails one of the checks.
+#[must_use]
+pub fn read_file_checked(folder: &str, day: Day, checks: &[InputCheck]) -> String {
+    try_read_file_checked(folder, day, checks).unwrap_or_else(|e| panic!("{e}"))
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{normalize, InputCheck, InputError};
+
+    #[test]
+    fn normalizes_line_endings() {
+        assert_eq!(normalize("ab\r\ncd\r\n"), "ab\ncd\n");
+    }
+
+    #[test]
+    fn normalizes_trailing_blank_lines() {
+        assert_eq!(normalize("ab\ncd\n\n\n  \n"), "ab\ncd\n");
+        assert_eq!(normalize("ab\ncd"), "ab\ncd\n");
+    }
+
+    #[test]
+    fn normalizes_byte_order_mark() {
+        assert_eq!(normalize("\u{feff}ab\n"), "ab\n");
+    }
+
+    #[test]
+    fn keeps_inner_blank_lines() {
+        assert_eq!(normalize("ab\n\ncd\n"), "ab\n\ncd\n");
+    }
+
+    #[test]
+    fn normalizes_empty_files() {
+        assert_eq!(normalize(""), "");
+        assert_eq!(normalize("\r\n\n"), "");
+    }
+
+    #[test]
+    fn checks_equal_line_lengths() {
+        assert!(InputCheck::EqualLineLengths.check("abc\ndef\n").is_ok());
+        assert!(matches!(
+            InputCheck::EqualLineLengths.check("abc\ndef\ngh\n"),
+            Err(InputError::UnequalLineLengths {
+                line: 3,
+                expected: 3,
+                found: 2
+            })
+        ));
+    }
+
+    #[test]
+    fn checks_ascii() {
+        assert!(InputCheck::Ascii.check("abc\n").is_ok());
+        assert!(matches!(
+            InputCheck::Ascii.check("abc\ndéf\n"),
+            Err(InputError::NonAscii { line: 2 })
+        ));
+    }
+}
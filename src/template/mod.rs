@@ -1,57 +1,63 @@
-use std::{env, fs};
-
+pub mod alloc;
 pub mod aoc_cli;
+pub mod cli;
 pub mod commands;
 pub mod runner;
 
 pub use day::*;
+pub use day_set::*;
+pub use input::*;
 
+mod answers;
+mod archive;
+mod compare;
 mod day;
+mod day_set;
+mod input;
+mod perf;
+mod readme;
 mod readme_benchmarks;
+mod readme_stars;
+mod readme_summary;
+mod report;
 mod run_multi;
+mod scaling;
 mod timings;
 
+#[cfg(all(feature = "dhat-heap", feature = "count-allocs"))]
+compile_error!(
+    "features `dhat-heap` and `count-allocs` both install a global allocator, enable only one."
+);
+
 pub const ANSI_ITALIC: &str = "\x1b[3m";
 pub const ANSI_BOLD: &str = "\x1b[1m";
 pub const ANSI_RESET: &str = "\x1b[0m";
 
-/// Helper function that reads a text file to a string.
-#[must_use]
-pub fn read_file(folder: &str, day: Day) -> String {
-    let cwd = env::current_dir().unwrap();
-    let filepath = cwd.join("data").join(folder).join(format!("{day}.txt"));
-    let f = fs::read_to_string(filepath);
-    f.expect("could not open input file")
-}
-
-/// Helper function that reads a text file to string, appending a part suffix. E.g. like `01-2.txt`.
-#[must_use]
-pub fn read_file_part(folder: &str, day: Day, part: u8) -> String {
-    let cwd = env::current_dir().unwrap();
-    let filepath = cwd
-        .join("data")
-        .join(folder)
-        .join(format!("{day}-{part}.txt"));
-    let f = fs::read_to_string(filepath);
-    f.expect("could not open input file")
-}
-
 /// Creates the constant `DAY` and sets up the input and runner for each part.
 ///
 /// The optional, second parameter (1 or 2) allows you to only run a single part of the solution.
+/// Alternatively, visual exports are registered by name, see `runner::Exports`:
+/// `dot = fn` is used by `--dot PATH`, `frames = fn` by `--frames DIR`.
 #[macro_export]
 macro_rules! solution {
     ($day:expr) => {
-        $crate::solution!(@impl $day, [part_one, 1] [part_two, 2]);
+        $crate::solution!(@impl $day, Default::default(), [part_one, 1] [part_two, 2]);
     };
     ($day:expr, 1) => {
-        $crate::solution!(@impl $day, [part_one, 1]);
+        $crate::solution!(@impl $day, Default::default(), [part_one, 1]);
     };
     ($day:expr, 2) => {
-        $crate::solution!(@impl $day, [part_two, 2]);
+        $crate::solution!(@impl $day, Default::default(), [part_two, 2]);
+    };
+    ($day:expr, $( $export:ident = $func:expr ),+ $(,)?) => {
+        $crate::solution!(
+            @impl $day,
+            $crate::template::runner::Exports { $( $export: Some($func), )+ ..Default::default() },
+            [part_one, 1] [part_two, 2]
+        );
     };
 
-    (@impl $day:expr, $( [$func:expr, $part:expr] )*) => {
+    (@impl $day:expr, $exports:expr, $( [$func:expr, $part:expr] )*) => {
         /// The current day.
         const DAY: $crate::template::Day = $crate::day!($day);
 
@@ -59,10 +65,17 @@ macro_rules! solution {
         #[global_allocator]
         static ALLOC: dhat::Alloc = dhat::Alloc;
 
+        #[cfg(feature = "count-allocs")]
+        #[global_allocator]
+        static ALLOC: $crate::template::alloc::CountingAlloc = $crate::template::alloc::CountingAlloc;
+
         fn main() {
             use $crate::template::runner::*;
+            let options = RunOptions::from_env();
             let input = $crate::template::read_file("inputs", DAY);
-            $( run_part($func, &input, DAY, $part); )*
+            let exports: Exports = $exports;
+            run_exports(&exports, &input, DAY, &options);
+            $( run_part($func, &input, DAY, $part, &options); )*
         }
     };
 }
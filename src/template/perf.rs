@@ -0,0 +1,223 @@
+/// Hardware performance counters of solution parts, read via `perf_event_open` on Linux.
+use std::fmt::Display;
+
+/// Counters per iteration of a part. Counters the CPU or kernel does not provide are `None`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct PerfCounters {
+    pub instructions: Option<u64>,
+    pub cycles: Option<u64>,
+    pub cache_misses: Option<u64>,
+    pub branch_misses: Option<u64>,
+}
+
+static NAMES: [&str; 4] = ["instructions", "cycles", "cache-misses", "branch-misses"];
+
+impl PerfCounters {
+    fn fields(&self) -> [Option<u64>; 4] {
+        [
+            self.instructions,
+            self.cycles,
+            self.cache_misses,
+            self.branch_misses,
+        ]
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.fields().iter().all(Option::is_none)
+    }
+
+    /// Parses the format of the `Display` impl, e.g. `1200 instructions, 800 cycles`.
+    pub fn parse(s: &str) -> Option<Self> {
+        let mut counters = PerfCounters::default();
+
+        for entry in s.split(", ") {
+            let (value, name) = entry.trim().split_once(' ')?;
+            let value = Some(value.parse().ok()?);
+            match name {
+                "instructions" => counters.instructions = value,
+                "cycles" => counters.cycles = value,
+                "cache-misses" => counters.cache_misses = value,
+                "branch-misses" => counters.branch_misses = value,
+                _ => return None,
+            }
+        }
+
+        Some(counters)
+    }
+}
+
+impl Display for PerfCounters {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let entries: Vec<String> = self
+            .fields()
+            .iter()
+            .zip(NAMES)
+            .filter_map(|(value, name)| value.map(|v| format!("{v} {name}")))
+            .collect();
+
+        write!(f, "{}", entries.join(", "))
+    }
+}
+
+#[cfg(all(feature = "perf-counters", target_os = "linux"))]
+pub use linux::Recorder;
+
+#[cfg(all(feature = "perf-counters", target_os = "linux"))]
+mod linux {
+    use super::PerfCounters;
+
+    const PERF_TYPE_HARDWARE: u32 = 0;
+    const PERF_COUNT_HW_CPU_CYCLES: u64 = 0;
+    const PERF_COUNT_HW_INSTRUCTIONS: u64 = 1;
+    const PERF_COUNT_HW_CACHE_MISSES: u64 = 3;
+    const PERF_COUNT_HW_BRANCH_MISSES: u64 = 5;
+
+    const PERF_EVENT_IOC_ENABLE: u64 = 0x2400;
+    const PERF_EVENT_IOC_DISABLE: u64 = 0x2401;
+    const PERF_EVENT_IOC_RESET: u64 = 0x2403;
+
+    // bits of the `perf_event_attr` flags bitfield.
+    const FLAG_DISABLED: u64 = 1 << 0;
+    const FLAG_EXCLUDE_KERNEL: u64 = 1 << 5;
+    const FLAG_EXCLUDE_HV: u64 = 1 << 6;
+
+    /// The first version of `struct perf_event_attr`, which every kernel accepts.
+    #[repr(C)]
+    #[derive(Default)]
+    struct PerfEventAttr {
+        kind: u32,
+        size: u32,
+        config: u64,
+        sample_period: u64,
+        sample_type: u64,
+        read_format: u64,
+        flags: u64,
+        wakeup_events: u32,
+        bp_type: u32,
+        config1: u64,
+    }
+
+    struct Counter(libc::c_int);
+
+    impl Counter {
+        fn open(config: u64) -> Option<Self> {
+            let attr = PerfEventAttr {
+                kind: PERF_TYPE_HARDWARE,
+                size: std::mem::size_of::<PerfEventAttr>() as u32,
+                config,
+                flags: FLAG_DISABLED | FLAG_EXCLUDE_KERNEL | FLAG_EXCLUDE_HV,
+                ..Default::default()
+            };
+
+            // measure the calling thread on any cpu.
+            let fd = unsafe {
+                libc::syscall(
+                    libc::SYS_perf_event_open,
+                    &attr as *const PerfEventAttr,
+                    0,
+                    -1,
+                    -1,
+                    0,
+                )
+            };
+
+            (fd >= 0).then_some(Counter(fd as libc::c_int))
+        }
+
+        fn ioctl(&self, request: u64) {
+            unsafe {
+                libc::ioctl(self.0, request as _, 0);
+            }
+        }
+
+        fn read(&self) -> Option<u64> {
+            let mut value: u64 = 0;
+            let size = std::mem::size_of::<u64>();
+            let read = unsafe { libc::read(self.0, (&mut value as *mut u64).cast(), size) };
+            (read == size as isize).then_some(value)
+        }
+    }
+
+    impl Drop for Counter {
+        fn drop(&mut self) {
+            unsafe {
+                libc::close(self.0);
+            }
+        }
+    }
+
+    /// Records hardware counters of the current thread while resumed. Work on other threads,
+    /// e.g. of a rayon pool, is not counted, so parallel parts are under-counted.
+    pub struct Recorder([Option<Counter>; 4]);
+
+    impl Recorder {
+        /// Opens all counters. Returns `None` if perf events are unavailable altogether,
+        /// e.g. because of `perf_event_paranoid` or missing support in a VM.
+        pub fn new() -> Option<Self> {
+            let counters = [
+                PERF_COUNT_HW_INSTRUCTIONS,
+                PERF_COUNT_HW_CPU_CYCLES,
+                PERF_COUNT_HW_CACHE_MISSES,
+                PERF_COUNT_HW_BRANCH_MISSES,
+            ]
+            .map(Counter::open);
+
+            counters
+                .iter()
+                .any(Option::is_some)
+                .then_some(Recorder(counters))
+        }
+
+        pub fn reset(&self) {
+            for counter in self.0.iter().flatten() {
+                counter.ioctl(PERF_EVENT_IOC_RESET);
+            }
+        }
+
+        pub fn resume(&self) {
+            for counter in self.0.iter().flatten() {
+                counter.ioctl(PERF_EVENT_IOC_ENABLE);
+            }
+        }
+
+        pub fn pause(&self) {
+            for counter in self.0.iter().flatten() {
+                counter.ioctl(PERF_EVENT_IOC_DISABLE);
+            }
+        }
+
+        /// Returns the counts since the last reset divided by `iterations`.
+        pub fn read(&self, iterations: u64) -> PerfCounters {
+            let [instructions, cycles, cache_misses, branch_misses] = self.0.each_ref().map(|c| {
+                c.as_ref()
+                    .and_then(Counter::read)
+                    .map(|v| v / iterations.max(1))
+            });
+
+            PerfCounters {
+                instructions,
+                cycles,
+                cache_misses,
+                branch_misses,
+            }
+        }
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::PerfCounters;
+
+    #[test]
+    fn roundtrips_counters() {
+        let counters = PerfCounters {
+            instructions: Some(1200),
+            cycles: Some(800),
+            cache_misses: None,
+            branch_misses: Some(3),
+        };
+        let s = counters.to_string();
+        assert_eq!(s, "1200 instructions, 800 cycles, 3 branch-misses");
+        assert_eq!(PerfCounters::parse(&s), Some(counters));
+    }
+}
@@ -0,0 +1,157 @@
+/// Module that keeps generated sections of the readme up to date.
+/// Each section lives between a pair of `<!--- name --->` markers, similar to how `aoc-readme-stars` handles this.
+use std::{fmt::Display, fs, io};
+
+static README_PATH: &str = "README.md";
+
+#[derive(Debug)]
+pub enum Error {
+    Parser(String),
+    IO(io::Error),
+    OutOfDate(Vec<&'static str>),
+}
+
+impl From<std::io::Error> for Error {
+    fn from(e: std::io::Error) -> Self {
+        Error::IO(e)
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::Parser(e) => write!(f, "{e}"),
+            Error::IO(e) => write!(f, "{e}"),
+            Error::OutOfDate(names) => {
+                write!(f, "README sections are out of date: {}", names.join(", "))
+            }
+        }
+    }
+}
+
+/// A generated section of the readme.
+pub trait Section {
+    /// Name of the section, used to build its markers.
+    fn name(&self) -> &'static str;
+
+    /// Content placed between the markers.
+    fn render(&self) -> String;
+}
+
+pub struct TablePosition {
+    pub pos_start: usize,
+    pub pos_end: usize,
+}
+
+pub fn marker(name: &str) -> String {
+    format!("<!--- {name} --->")
+}
+
+/// Whether the readme contains markers for a section.
+pub fn has_section(readme: &str, name: &str) -> bool {
+    readme.contains(&marker(name))
+}
+
+/// Locates a section, including its markers.
+fn locate_section(readme: &str, name: &str) -> Result<TablePosition, Error> {
+    let marker = marker(name);
+    let matches: Vec<_> = readme.match_indices(&marker).collect();
+
+    if matches.len() > 2 {
+        return Err(Error::Parser(format!(
+            "{marker}: too many occurences of marker in README."
+        )));
+    }
+
+    let pos_start = matches
+        .first()
+        .map(|m| m.0)
+        .ok_or_else(|| Error::Parser(format!("{marker}: could not find table start position.")))?;
+
+    let pos_end = matches
+        .last()
+        .map(|m| m.0 + m.1.len())
+        .ok_or_else(|| Error::Parser(format!("{marker}: could not find table end position.")))?;
+
+    Ok(TablePosition { pos_start, pos_end })
+}
+
+/// Replaces a section with its rendered content. Returns whether the content changed.
+pub fn update_section(s: &mut String, section: &dyn Section) -> Result<bool, Error> {
+    let positions = locate_section(s, section.name())?;
+    let marker = marker(section.name());
+    let rendered = format!("{marker}\n{}\n{marker}", section.render());
+
+    let is_changed = s[positions.pos_start..positions.pos_end] != rendered;
+    s.replace_range(positions.pos_start..positions.pos_end, &rendered);
+    Ok(is_changed)
+}
+
+/// Renders all sections into the readme.
+/// With `check`, the readme is left untouched and outdated sections are returned as an error.
+pub fn update(sections: &[&dyn Section], check: bool) -> Result<(), Error> {
+    let mut readme = read()?;
+    let mut outdated = vec![];
+
+    for section in sections {
+        if update_section(&mut readme, *section)? {
+            outdated.push(section.name());
+        }
+    }
+
+    if check {
+        if outdated.is_empty() {
+            return Ok(());
+        }
+        return Err(Error::OutOfDate(outdated));
+    }
+
+    fs::write(README_PATH, &readme)?;
+    Ok(())
+}
+
+pub fn read() -> Result<String, Error> {
+    Ok(String::from_utf8_lossy(&fs::read(README_PATH)?).to_string())
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{marker, update_section, Section};
+
+    struct Greeting(&'static str);
+
+    impl Section for Greeting {
+        fn name(&self) -> &'static str {
+            "greeting"
+        }
+
+        fn render(&self) -> String {
+            format!("hello {}", self.0)
+        }
+    }
+
+    #[test]
+    fn updates_named_sections() {
+        let mut s = format!(
+            "foo\n{0}{0}\nbar\n{1}\nkeep\n{1}",
+            marker("greeting"),
+            marker("other")
+        );
+        assert!(update_section(&mut s, &Greeting("world")).unwrap());
+        assert!(!update_section(&mut s, &Greeting("world")).unwrap());
+        assert_eq!(
+            s,
+            format!(
+                "foo\n{0}\nhello world\n{0}\nbar\n{1}\nkeep\n{1}",
+                marker("greeting"),
+                marker("other")
+            )
+        );
+    }
+
+    #[test]
+    fn errors_for_missing_markers() {
+        let mut s = "# readme".to_string();
+        assert!(update_section(&mut s, &Greeting("world")).is_err());
+    }
+}
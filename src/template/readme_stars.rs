@@ -0,0 +1,141 @@
+/// Module that updates the readme with the stars collected so far.
+/// Replaces the `aoc-readme-stars` action, so no session is needed to keep the table current.
+use crate::template::answers::Answers;
+use crate::template::readme::{self, Error, Section};
+use crate::template::Day;
+
+static NAME: &str = "advent_readme_stars table";
+
+/// Per-part completion for a single day.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct DayStars {
+    pub day: Day,
+    pub part_1: bool,
+    pub part_2: bool,
+}
+
+impl DayStars {
+    pub fn count(&self) -> usize {
+        usize::from(self.part_1) + usize::from(self.part_2)
+    }
+}
+
+/// Derives completion from answers. Days without any answer are left out.
+pub fn from_answers(answers: &Answers) -> Vec<DayStars> {
+    answers
+        .data
+        .iter()
+        .map(|answer| DayStars {
+            day: answer.day,
+            part_1: answer.part_1.is_some(),
+            part_2: answer.part_2.is_some(),
+        })
+        .filter(|stars| stars.part_1 || stars.part_2)
+        .collect()
+}
+
+/// Table of the stars collected per day.
+pub struct Stars {
+    pub year: u16,
+    pub days: Vec<DayStars>,
+}
+
+impl Section for Stars {
+    fn name(&self) -> &'static str {
+        NAME
+    }
+
+    fn render(&self) -> String {
+        construct_table("##", self.year, &self.days)
+    }
+}
+
+fn construct_table(prefix: &str, year: u16, stars: &[DayStars]) -> String {
+    let header = format!("{prefix} {year} Results");
+    let star = |is_solved: bool| if is_solved { "⭐" } else { " " };
+
+    let mut lines: Vec<String> = vec![
+        header,
+        String::new(),
+        "| Day | Part 1 | Part 2 |".into(),
+        "| :---: | :---: | :---: |".into(),
+    ];
+
+    for day in stars {
+        let day_number = day.day.into_inner();
+        lines.push(format!(
+            "| [Day {day_number}](https://adventofcode.com/{year}/day/{day_number}) | {} | {} |",
+            star(day.part_1),
+            star(day.part_2)
+        ));
+    }
+
+    lines.join("\n")
+}
+
+pub fn update(stars: Stars) -> Result<(), Error> {
+    readme::update(&[&stars], false)
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{from_answers, Stars, NAME};
+    use crate::{
+        day,
+        template::answers::{Answer, Answers},
+        template::readme::{marker, update_section},
+    };
+
+    fn get_mock_answers() -> Answers {
+        Answers {
+            data: vec![
+                Answer {
+                    day: day!(1),
+                    part_1: Some("11".into()),
+                    part_2: Some("31".into()),
+                },
+                Answer {
+                    day: day!(2),
+                    part_1: None,
+                    part_2: None,
+                },
+                Answer {
+                    day: day!(14),
+                    part_1: Some("12".into()),
+                    part_2: None,
+                },
+            ],
+        }
+    }
+
+    #[test]
+    fn derives_stars_from_answers() {
+        let stars = from_answers(&get_mock_answers());
+        assert_eq!(stars.len(), 2);
+        assert_eq!((stars[1].part_1, stars[1].part_2), (true, false));
+    }
+
+    #[test]
+    fn format_stars() {
+        let mut s = format!("foo\n{}\n{}\nbar", marker(NAME), marker(NAME));
+        let stars = Stars {
+            year: 2024,
+            days: from_answers(&get_mock_answers()),
+        };
+        update_section(&mut s, &stars).unwrap();
+        let expected = [
+            "foo",
+            "<!--- advent_readme_stars table --->",
+            "## 2024 Results",
+            "",
+            "| Day | Part 1 | Part 2 |",
+            "| :---: | :---: | :---: |",
+            "| [Day 1](https://adventofcode.com/2024/day/1) | ⭐ | ⭐ |",
+            "| [Day 14](https://adventofcode.com/2024/day/14) | ⭐ |   |",
+            "<!--- advent_readme_stars table --->",
+            "bar",
+        ]
+        .join("\n");
+        assert_eq!(s, expected);
+    }
+}
@@ -0,0 +1,182 @@
+/// Module with overview sections for the readme, combining the answer ledger and stored timings.
+use crate::template::readme::Section;
+use crate::template::readme_benchmarks::get_path_for_bin;
+use crate::template::readme_stars::DayStars;
+use crate::template::timings::Timings;
+
+/// Table with the stars and total run time of each day.
+pub struct DaySummary<'a> {
+    pub stars: &'a [DayStars],
+    pub timings: &'a Timings,
+}
+
+impl Section for DaySummary<'_> {
+    fn name(&self) -> &'static str {
+        "day summary"
+    }
+
+    fn render(&self) -> String {
+        let mut lines: Vec<String> = vec![
+            "## Summary".into(),
+            String::new(),
+            "| Day | Stars | Total |".into(),
+            "| :---: | :---: | :---: |".into(),
+        ];
+
+        let mut days: Vec<_> = self
+            .stars
+            .iter()
+            .map(|s| s.day)
+            .chain(self.timings.data.iter().map(|t| t.day))
+            .collect();
+        days.sort_unstable();
+        days.dedup();
+
+        for day in days {
+            let stars = self
+                .stars
+                .iter()
+                .find(|s| s.day == day)
+                .map_or(0, DayStars::count);
+
+            let total = self
+                .timings
+                .get(day)
+                .map_or_else(|| "-".into(), |t| format_millis(t.total_nanos));
+
+            lines.push(format!(
+                "| [Day {}]({}) | {} | `{total}` |",
+                day.into_inner(),
+                get_path_for_bin(day),
+                "⭐".repeat(stars)
+            ));
+        }
+
+        lines.join("\n")
+    }
+}
+
+/// Ranked table of the days with the longest total run time.
+pub struct SlowestDays<'a> {
+    pub timings: &'a Timings,
+    pub limit: usize,
+}
+
+impl Section for SlowestDays<'_> {
+    fn name(&self) -> &'static str {
+        "slowest days"
+    }
+
+    fn render(&self) -> String {
+        let mut lines: Vec<String> = vec![
+            "## Slowest Days".into(),
+            String::new(),
+            "| Rank | Day | Total |".into(),
+            "| :---: | :---: | :---: |".into(),
+        ];
+
+        let mut timings: Vec<_> = self.timings.data.iter().collect();
+        timings.sort_unstable_by(|a, b| b.total_nanos.total_cmp(&a.total_nanos));
+
+        for (rank, timing) in timings.into_iter().take(self.limit).enumerate() {
+            lines.push(format!(
+                "| {} | [Day {}]({}) | `{}` |",
+                rank + 1,
+                timing.day.into_inner(),
+                get_path_for_bin(timing.day),
+                format_millis(timing.total_nanos)
+            ));
+        }
+
+        lines.join("\n")
+    }
+}
+
+fn format_millis(nanos: f64) -> String {
+    format!("{:.2}ms", nanos / 1_000_000_f64)
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{DaySummary, SlowestDays};
+    use crate::{
+        day,
+        template::readme::Section,
+        template::readme_stars::DayStars,
+        template::timings::{Timing, Timings},
+    };
+
+    fn get_mock_timings() -> Timings {
+        Timings {
+            data: vec![
+                Timing {
+                    day: day!(1),
+                    part_1: Some("10ms".into()),
+                    part_2: Some("20ms".into()),
+                    total_nanos: 3e+7,
+                    threads: None,
+                    metrics: Default::default(),
+                },
+                Timing {
+                    day: day!(2),
+                    part_1: Some("30ms".into()),
+                    part_2: Some("40ms".into()),
+                    total_nanos: 7e+7,
+                    threads: None,
+                    metrics: Default::default(),
+                },
+                Timing {
+                    day: day!(4),
+                    part_1: Some("1ms".into()),
+                    part_2: None,
+                    total_nanos: 1e+6,
+                    threads: None,
+                    metrics: Default::default(),
+                },
+            ],
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn renders_day_summary() {
+        let stars = [DayStars {
+            day: day!(1),
+            part_1: true,
+            part_2: true,
+        }];
+        let timings = get_mock_timings();
+        let rendered = DaySummary {
+            stars: &stars,
+            timings: &timings,
+        }
+        .render();
+        let lines: Vec<_> = rendered.lines().skip(4).collect();
+        assert_eq!(
+            lines,
+            [
+                "| [Day 1](./src/bin/01.rs) | ⭐⭐ | `30.00ms` |",
+                "| [Day 2](./src/bin/02.rs) |  | `70.00ms` |",
+                "| [Day 4](./src/bin/04.rs) |  | `1.00ms` |",
+            ]
+        );
+    }
+
+    #[test]
+    fn renders_slowest_days() {
+        let timings = get_mock_timings();
+        let rendered = SlowestDays {
+            timings: &timings,
+            limit: 2,
+        }
+        .render();
+        let lines: Vec<_> = rendered.lines().skip(4).collect();
+        assert_eq!(
+            lines,
+            [
+                "| 1 | [Day 2](./src/bin/02.rs) | `70.00ms` |",
+                "| 2 | [Day 1](./src/bin/01.rs) | `30.00ms` |",
+            ]
+        );
+    }
+}
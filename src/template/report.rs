@@ -0,0 +1,247 @@
+/// Module that renders benchmark timings into standalone report files.
+use std::{fmt::Write, str::FromStr};
+
+use tinyjson::JsonValue;
+
+use crate::template::readme_benchmarks::construct_table;
+use crate::template::timings::Timings;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReportFormat {
+    Csv,
+    JsonLines,
+    Markdown,
+    Html,
+}
+
+impl FromStr for ReportFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "csv" => Ok(ReportFormat::Csv),
+            "jsonl" => Ok(ReportFormat::JsonLines),
+            "md" | "markdown" => Ok(ReportFormat::Markdown),
+            "html" => Ok(ReportFormat::Html),
+            _ => Err("expecting one of `csv`, `jsonl`, `md` or `html`".into()),
+        }
+    }
+}
+
+pub fn render(format: ReportFormat, timings: &Timings) -> String {
+    match format {
+        ReportFormat::Csv => render_csv(timings),
+        ReportFormat::JsonLines => render_json_lines(timings),
+        ReportFormat::Markdown => render_markdown(timings),
+        ReportFormat::Html => render_html(timings),
+    }
+}
+
+fn format_nanos(nanos: Option<f64>) -> String {
+    nanos.map(|n| format!("{n:.0}")).unwrap_or_default()
+}
+
+fn render_csv(timings: &Timings) -> String {
+    let mut csv = String::from("day,part_1,part_2,part_1_nanos,part_2_nanos,total_nanos\n");
+
+    for timing in &timings.data {
+        let _ = writeln!(
+            csv,
+            "{},{},{},{},{},{:.0}",
+            timing.day,
+            timing.part_1.as_deref().unwrap_or_default(),
+            timing.part_2.as_deref().unwrap_or_default(),
+            format_nanos(timing.part_nanos(1)),
+            format_nanos(timing.part_nanos(2)),
+            timing.total_nanos
+        );
+    }
+
+    csv
+}
+
+fn render_json_lines(timings: &Timings) -> String {
+    timings
+        .data
+        .iter()
+        .filter_map(|timing| JsonValue::from(timing).stringify().ok())
+        .map(|line| line + "\n")
+        .collect()
+}
+
+fn render_markdown(timings: &Timings) -> String {
+    construct_table("#", timings, timings.total_millis()) + "\n"
+}
+
+/* -------------------------------------------------------------------------- */
+
+static BAR_HEIGHT: usize = 14;
+static ROW_HEIGHT: usize = 40;
+static LABEL_WIDTH: usize = 70;
+static CHART_WIDTH: usize = 600;
+static PART_COLORS: [&str; 2] = ["#4c8bf5", "#f5a623"];
+
+fn escape_html(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// Horizontal bar chart with one row per day and one bar per part, scaled to the slowest part.
+fn render_svg(timings: &Timings) -> String {
+    let max_nanos = timings
+        .data
+        .iter()
+        .flat_map(|t| [t.part_nanos(1), t.part_nanos(2)])
+        .flatten()
+        .fold(0_f64, f64::max);
+
+    let width = LABEL_WIDTH + CHART_WIDTH + 100;
+    let height = ROW_HEIGHT * timings.data.len().max(1);
+
+    let mut svg = format!(
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{width}" height="{height}" viewBox="0 0 {width} {height}" font-family="sans-serif" font-size="12">"#
+    );
+
+    for (row, timing) in timings.data.iter().enumerate() {
+        let y = row * ROW_HEIGHT;
+        let _ = write!(
+            svg,
+            r#"<text x="0" y="{}">Day {}</text>"#,
+            y + ROW_HEIGHT / 2 + 4,
+            timing.day.into_inner()
+        );
+
+        for (i, part) in [1, 2].into_iter().enumerate() {
+            let bar_y = y + 4 + i * (BAR_HEIGHT + 2);
+            let label = timing.part(part).unwrap_or("-");
+            let bar_width = match timing.part_nanos(part) {
+                Some(nanos) if max_nanos > 0.0 => nanos / max_nanos * CHART_WIDTH as f64,
+                _ => 0.0,
+            };
+
+            let _ = write!(
+                svg,
+                r#"<rect x="{LABEL_WIDTH}" y="{bar_y}" width="{bar_width:.1}" height="{BAR_HEIGHT}" fill="{}"><title>Part {part}: {}</title></rect><text x="{:.1}" y="{}">{}</text>"#,
+                PART_COLORS[i],
+                escape_html(label),
+                LABEL_WIDTH as f64 + bar_width + 4.0,
+                bar_y + BAR_HEIGHT - 3,
+                escape_html(label)
+            );
+        }
+    }
+
+    svg.push_str("</svg>");
+    svg
+}
+
+fn render_html(timings: &Timings) -> String {
+    let mut rows = String::new();
+
+    for timing in &timings.data {
+        let _ = write!(
+            rows,
+            "<tr><td>Day {}</td><td>{}</td><td>{}</td><td>{:.2}ms</td></tr>",
+            timing.day.into_inner(),
+            escape_html(timing.part(1).unwrap_or("-")),
+            escape_html(timing.part(2).unwrap_or("-")),
+            timing.total_nanos / 1_000_000_f64
+        );
+    }
+
+    format!(
+        r#"<!DOCTYPE html>
+<html lang="en">
+<head>
+<meta charset="utf-8">
+<title>Benchmarks</title>
+<style>
+body {{ font-family: sans-serif; margin: 2rem; }}
+table {{ border-collapse: collapse; margin-bottom: 2rem; }}
+td, th {{ border: 1px solid #ccc; padding: 0.25rem 0.75rem; text-align: right; }}
+.legend span {{ display: inline-block; width: 0.8rem; height: 0.8rem; margin: 0 0.25rem 0 1rem; }}
+</style>
+</head>
+<body>
+<h1>Benchmarks</h1>
+<p class="legend"><span style="background: {}"></span>Part 1<span style="background: {}"></span>Part 2</p>
+{}
+<table>
+<tr><th>Day</th><th>Part 1</th><th>Part 2</th><th>Total</th></tr>
+{rows}
+</table>
+<p><strong>Total: {:.2}ms</strong></p>
+</body>
+</html>
+"#,
+        PART_COLORS[0],
+        PART_COLORS[1],
+        render_svg(timings),
+        timings.total_millis()
+    )
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{render, ReportFormat};
+    use crate::{
+        day,
+        template::timings::{Timing, Timings},
+    };
+
+    fn get_mock_timings() -> Timings {
+        Timings {
+            data: vec![
+                Timing {
+                    day: day!(1),
+                    part_1: Some("10ms".into()),
+                    part_2: Some("20ms".into()),
+                    total_nanos: 3e+7,
+                    threads: None,
+                    metrics: Default::default(),
+                },
+                Timing {
+                    day: day!(2),
+                    part_1: Some("5µs".into()),
+                    part_2: None,
+                    total_nanos: 5e+3,
+                    threads: None,
+                    metrics: Default::default(),
+                },
+            ],
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn renders_csv() {
+        let csv = render(ReportFormat::Csv, &get_mock_timings());
+        assert_eq!(
+            csv.lines().collect::<Vec<_>>(),
+            [
+                "day,part_1,part_2,part_1_nanos,part_2_nanos,total_nanos",
+                "01,10ms,20ms,10000000,20000000,30000000",
+                "02,5µs,,5000,,5000",
+            ]
+        );
+    }
+
+    #[test]
+    fn renders_json_lines() {
+        let jsonl = render(ReportFormat::JsonLines, &get_mock_timings());
+        assert_eq!(jsonl.lines().count(), 2);
+        assert!(jsonl
+            .lines()
+            .all(|l| l.starts_with('{') && l.ends_with('}')));
+    }
+
+    #[test]
+    fn renders_html_with_chart() {
+        let html = render(ReportFormat::Html, &get_mock_timings());
+        assert!(html.contains("<svg"));
+        assert_eq!(html.matches("<rect").count(), 4);
+        assert!(html.contains(r#"width="600.0""#));
+    }
+}
@@ -1,48 +1,125 @@
-use std::{collections::HashSet, io};
+use std::io;
 
-use crate::template::{Day, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
+use crate::template::{Day, DaySet, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
 
 use super::{
-    all_days,
+    answers::{Answer, Answers},
     timings::{Timing, Timings},
 };
 
-pub fn run_multi(days_to_run: &HashSet<Day>, is_release: bool, is_timed: bool) -> Option<Timings> {
-    let mut timings: Vec<Timing> = Vec::with_capacity(days_to_run.len());
+/// Results collected from the output of each solution binary.
+pub struct RunResults {
+    pub answers: Answers,
+    pub timings: Option<Timings>,
+}
 
-    let mut need_space = false;
+/// Cargo profile used to build solution binaries.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BuildProfile {
+    Debug,
+    Release,
+    /// Release build with the `dhat-heap` feature, reporting allocations per part.
+    Dhat,
+    /// Release build with the `count-allocs` feature, reporting allocations per part.
+    CountAllocs,
+    /// Release build with the `perf-counters` feature, reporting hardware counters per part.
+    PerfCounters,
+    /// Release build with debug symbols, for external profilers.
+    Profiling,
+}
 
-    // NOTE: use non-duplicate, sorted day values.
-    all_days()
-        .filter(|day| days_to_run.contains(day))
-        .for_each(|day| {
-            if need_space {
-                println!();
-            }
-            need_space = true;
+impl BuildProfile {
+    pub fn from_release(is_release: bool) -> Self {
+        if is_release {
+            BuildProfile::Release
+        } else {
+            BuildProfile::Debug
+        }
+    }
+
+    /// Whether durations are skewed by allocation tracking.
+    pub fn is_profiling(self) -> bool {
+        matches!(self, BuildProfile::Dhat | BuildProfile::CountAllocs)
+    }
+
+    pub fn cargo_args(self) -> Vec<String> {
+        match self {
+            BuildProfile::Debug => vec![],
+            BuildProfile::Release => vec!["--release".into()],
+            BuildProfile::Dhat => vec![
+                "--profile".into(),
+                "dhat".into(),
+                "--features".into(),
+                "dhat-heap".into(),
+            ],
+            BuildProfile::CountAllocs => vec![
+                "--release".into(),
+                "--features".into(),
+                "count-allocs".into(),
+            ],
+            BuildProfile::PerfCounters => vec![
+                "--release".into(),
+                "--features".into(),
+                "perf-counters".into(),
+            ],
+            BuildProfile::Profiling => vec!["--profile".into(), "profiling".into()],
+        }
+    }
+}
+
+/// Runs the selected days. `threads` sizes the rayon pool of each solution, if set.
+pub fn run_multi(
+    days_to_run: &DaySet,
+    profile: BuildProfile,
+    is_timed: bool,
+    threads: Option<usize>,
+) -> RunResults {
+    let days = days_to_run.resolve();
 
-            println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
-            println!("------");
+    let mut timings: Vec<Timing> = Vec::with_capacity(days.len());
+    let mut answers: Vec<Answer> = Vec::with_capacity(days.len());
 
-            let output = child_commands::run_solution(day, is_timed, is_release).unwrap();
+    let mut need_space = false;
 
-            if output.is_empty() {
-                println!("Not solved.");
-            } else {
-                let val = child_commands::parse_exec_time(&output, day);
-                timings.push(val);
+    days.into_iter().for_each(|day| {
+        if need_space {
+            println!();
+        }
+        need_space = true;
+
+        println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
+        println!("------");
+
+        let output = child_commands::run_solution(day, is_timed, profile, threads).unwrap();
+
+        if output.is_empty() {
+            println!("Not solved.");
+        } else {
+            answers.push(child_commands::parse_answers(&output, day));
+            if is_timed {
+                timings.push(Timing {
+                    threads,
+                    ..child_commands::parse_exec_time(&output, day)
+                });
             }
-        });
+        }
+    });
 
-    if is_timed {
-        let timings = Timings { data: timings };
+    let timings = is_timed.then(|| {
+        let timings = Timings {
+            data: timings,
+            ..Default::default()
+        };
         let total_millis = timings.total_millis();
         println!(
             "\n{ANSI_BOLD}Total (Run):{ANSI_RESET} {ANSI_ITALIC}{total_millis:.2}ms{ANSI_RESET}"
         );
-        Some(timings)
-    } else {
-        None
+        timings
+    });
+
+    RunResults {
+        answers: Answers { data: answers },
+        timings,
     }
 }
 
@@ -67,8 +144,11 @@ pub fn get_path_for_bin(day: Day) -> String {
 /// All solutions live in isolated binaries.
 /// This module encapsulates interaction with these binaries, both invoking them as well as parsing the timing output.
 pub mod child_commands {
-    use super::{get_path_for_bin, Error};
-    use crate::template::Day;
+    use super::{get_path_for_bin, BuildProfile, Error};
+    use crate::template::alloc::AllocStats;
+    use crate::template::perf::PerfCounters;
+    use crate::template::timings::{parse_duration_nanos, PartMetrics};
+    use crate::template::{runner::RunOptions, Day, ANSI_BOLD, ANSI_RESET};
     use std::{
         io::{BufRead, BufReader},
         path::Path,
@@ -77,33 +157,47 @@ pub mod child_commands {
     };
 
     /// Run the solution bin for a given day
-    pub fn run_solution(day: Day, is_timed: bool, is_release: bool) -> Result<Vec<String>, Error> {
+    pub fn run_solution(
+        day: Day,
+        is_timed: bool,
+        profile: BuildProfile,
+        threads: Option<usize>,
+    ) -> Result<Vec<String>, Error> {
         // skip command invocation for days that have not been scaffolded yet.
         if !Path::new(&get_path_for_bin(day)).exists() {
             return Ok(vec![]);
         }
 
-        let day_padded = day.to_string();
-        let mut args = vec!["run", "--quiet", "--bin", &day_padded];
+        let mut args = vec![
+            "run".to_string(),
+            "--quiet".to_string(),
+            "--bin".to_string(),
+            day.to_string(),
+        ];
 
-        if is_release {
-            args.push("--release");
-        }
+        args.extend(profile.cargo_args());
 
-        if is_timed {
-            // mirror `--time` flag to child invocations.
-            args.push("--");
-            args.push("--time");
-        }
+        let options = RunOptions {
+            time: is_timed,
+            ..Default::default()
+        };
+        args.push("--".to_string());
+        args.extend(options.to_args());
 
         // spawn child command with piped stdout/stderr.
         // forward output to stdout/stderr while grabbing stdout lines.
 
-        let mut cmd = Command::new("cargo")
-            .args(&args)
+        let mut cmd = Command::new("cargo");
+        cmd.args(&args)
             .stdout(Stdio::piped())
-            .stderr(Stdio::piped())
-            .spawn()?;
+            .stderr(Stdio::piped());
+
+        // rayon sizes its global pool from this variable.
+        if let Some(threads) = threads {
+            cmd.env("RAYON_NUM_THREADS", threads.to_string());
+        }
+
+        let mut cmd = cmd.spawn()?;
 
         let stdout = BufReader::new(cmd.stdout.take().ok_or(super::Error::BrokenPipe)?);
         let stderr = BufReader::new(cmd.stderr.take().ok_or(super::Error::BrokenPipe)?);
@@ -134,59 +228,115 @@ pub mod child_commands {
             part_1: None,
             part_2: None,
             total_nanos: 0_f64,
+            threads: None,
+            metrics: Default::default(),
         };
 
         output
             .iter()
             .filter_map(|l| {
-                if !l.contains(" samples)") {
+                if !l.contains(" samples") {
                     return None;
                 }
 
-                let Some((timing_str, nanos)) = parse_time(l) else {
+                let Some(timing) = parse_time(l) else {
                     eprintln!("Could not parse timings from line: {l}");
                     return None;
                 };
 
                 let part = l.split(':').next()?;
-                Some((part, timing_str, nanos))
+                Some((part, timing))
             })
-            .for_each(|(part, timing_str, nanos)| {
+            .for_each(|(part, (timing_str, nanos, metrics))| {
                 if part.contains("Part 1") {
                     timings.part_1 = Some(timing_str.into());
+                    timings.metrics[0] = metrics;
                 } else if part.contains("Part 2") {
                     timings.part_2 = Some(timing_str.into());
+                    timings.metrics[1] = metrics;
                 }
 
                 timings.total_nanos += nanos;
             });
 
+        for line in output {
+            let Some((part, kind)) = line.split_once(' ').and_then(|(part, rest)| {
+                let (number, kind) = rest.split_once(' ')?;
+                Some((format!("{part} {number}"), kind))
+            }) else {
+                continue;
+            };
+
+            let index = match part.as_str() {
+                "Part 1" => 0,
+                "Part 2" => 1,
+                _ => continue,
+            };
+
+            if let Some(counters) = kind.strip_prefix("counters (main thread): ") {
+                timings.metrics[index].counters = PerfCounters::parse(counters);
+            }
+        }
+
         timings
     }
 
-    fn parse_to_float(s: &str, postfix: &str) -> Option<f64> {
-        s.split(postfix).next()?.parse().ok()
+    /// Parse the results printed by `runner::print_result`, e.g. `Part 1: 42 (1.2ms)`.
+    /// Multi-line results are not supported and are treated as missing.
+    pub fn parse_answers(output: &[String], day: Day) -> super::Answer {
+        let mut answer = super::Answer {
+            day,
+            part_1: None,
+            part_2: None,
+        };
+
+        for line in output {
+            // intermediate results are overwritten with a carriage return, only keep the final one.
+            let line = line.rsplit('\r').next().unwrap_or(line);
+
+            let Some((part, rest)) = line.split_once(": ") else {
+                continue;
+            };
+
+            let result = rest
+                .strip_prefix(ANSI_BOLD)
+                .and_then(|r| r.split_once(ANSI_RESET))
+                .map(|(result, _)| result.to_string());
+
+            match part {
+                "Part 1" => answer.part_1 = result,
+                "Part 2" => answer.part_2 = result,
+                _ => {}
+            }
+        }
+
+        answer
     }
 
-    fn parse_time(line: &str) -> Option<(&str, f64)> {
-        // for possible time formats, see: https://github.com/rust-lang/rust/blob/1.64.0/library/core/src/time.rs#L1176-L1200
-        let str_timing = line
-            .split(" samples)")
-            .next()?
-            .split('(')
-            .last()?
-            .split('@')
-            .next()?
-            .trim();
-
-        let parsed_timing = match str_timing {
-            s if s.contains("ns") => s.split("ns").next()?.parse::<f64>().ok(),
-            s if s.contains("µs") => parse_to_float(s, "µs").map(|x| x * 1000_f64),
-            s if s.contains("ms") => parse_to_float(s, "ms").map(|x| x * 1_000_000_f64),
-            s => parse_to_float(s, "s").map(|x| x * 1_000_000_000_f64),
-        }?;
-
-        Some((str_timing, parsed_timing))
+    /// Parses the bench output of a part, e.g. `(1.2ms ± 30.5µs @ 100 samples, 340 allocs, 2.1MB peak)`.
+    /// The standard deviation is missing in output of older versions.
+    fn parse_time(line: &str) -> Option<(&str, f64, PartMetrics)> {
+        let summary = line.rsplit('(').next()?.trim_end().strip_suffix(')')?;
+        let (str_stats, allocations) = match summary.split_once(", ") {
+            Some((stats, allocations)) => (stats, AllocStats::parse(allocations)),
+            None => (summary, None),
+        };
+        let (str_stats, str_samples) = str_stats.strip_suffix(" samples")?.split_once('@')?;
+
+        let (str_timing, str_stddev) = match str_stats.split_once('±') {
+            Some((timing, stddev)) => (timing.trim(), Some(stddev.trim())),
+            None => (str_stats.trim(), None),
+        };
+
+        let parsed_timing = parse_duration_nanos(str_timing)?;
+        let metrics = PartMetrics {
+            stddev_nanos: str_stddev.and_then(parse_duration_nanos),
+            samples: str_samples.trim().parse().ok(),
+            allocations,
+            counters: None,
+        };
+
+        Some((str_timing, parsed_timing, metrics))
     }
 
     /// copied from: https://github.com/rust-lang/rust/blob/1.64.0/library/std/src/macros.rs#L328-L333
@@ -205,7 +355,7 @@ pub mod child_commands {
 
     #[cfg(feature = "test_lib")]
     mod tests {
-        use super::parse_exec_time;
+        use super::{parse_answers, parse_exec_time};
 
         use crate::day;
 
@@ -239,6 +389,71 @@ pub mod child_commands {
             assert_eq!(res.part_2.unwrap(), "100ms");
         }
 
+        #[test]
+        fn parses_distribution_metrics() {
+            let res = parse_exec_time(
+                &[
+                    "Part 1: 0 (1.5ms ± 20.0µs @ 640 samples)".into(),
+                    "Part 2: 10 (74.13ms @ 99999 samples)".into(),
+                    "".into(),
+                ],
+                day!(1),
+            );
+            assert_eq!(res.part_1.unwrap(), "1.5ms");
+            assert_approx_eq!(res.metrics[0].stddev_nanos.unwrap(), 20000_f64);
+            assert_eq!(res.metrics[0].samples, Some(640));
+            assert_eq!(res.metrics[1].stddev_nanos, None);
+            assert_eq!(res.metrics[1].samples, Some(99999));
+        }
+
+        #[test]
+        fn parses_allocations() {
+            let res = parse_exec_time(
+                &[
+                    "Part 1: 0 (1.5ms ± 20.0µs @ 640 samples, 2.1MB in 340 allocs, 1.0KB peak in 12 blocks)".into(),
+                    "Part 2: 10 (74.13ms @ 99999 samples, 3 allocs, 1.0KB peak)".into(),
+                    "".into(),
+                ],
+                day!(1),
+            );
+            assert_eq!(res.part_2.unwrap(), "74.13ms");
+            assert_eq!(res.metrics[1].samples, Some(99999));
+            let allocations = res.metrics[0].allocations.unwrap();
+            assert_eq!(allocations.total_bytes, 2_100_000);
+            assert_eq!(allocations.peak_blocks, 12);
+            assert_eq!(res.metrics[1].allocations, None);
+        }
+
+        #[test]
+        fn parses_counters() {
+            let res = parse_exec_time(
+                &[
+                    "Part 1: 0 (1.5ms ± 20.0µs @ 640 samples)".into(),
+                    "Part 1 counters (main thread): 1200 instructions, 800 cycles".into(),
+                    "".into(),
+                ],
+                day!(1),
+            );
+            let counters = res.metrics[0].counters.unwrap();
+            assert_eq!(counters.instructions, Some(1200));
+            assert_eq!(counters.branch_misses, None);
+        }
+
+        #[test]
+        fn parses_answers() {
+            let res = parse_answers(
+                &[
+                    "Part 1: \x1b[1m11\x1b[0m\rPart 1: \x1b[1m11\x1b[0m (65.5µs @ 10000 samples)"
+                        .into(),
+                    "Part 2: ✖\rPart 2: ✖             ".into(),
+                    "".into(),
+                ],
+                day!(1),
+            );
+            assert_eq!(res.part_1.unwrap(), "11");
+            assert_eq!(res.part_2.is_none(), true);
+        }
+
         #[test]
         fn parses_missing_parts() {
             let res = parse_exec_time(
@@ -2,57 +2,346 @@
 use std::fmt::Display;
 use std::hint::black_box;
 use std::io::{stdout, Write};
+use std::path::Path;
 use std::process::Output;
+use std::sync::OnceLock;
 use std::time::{Duration, Instant};
-use std::{cmp, env, process};
+use std::{cmp, env, fs, process};
 
+use crate::dot::Graph;
+use crate::frame::{Frame, FrameFormat};
+use crate::template::alloc::AllocStats;
+#[cfg(feature = "count-allocs")]
+use crate::template::alloc::CountingAlloc;
+use crate::template::cli::{self, Arg, Command, Matches};
+#[cfg(all(feature = "perf-counters", target_os = "linux"))]
+use crate::template::perf;
+use crate::template::perf::PerfCounters;
+use crate::template::timings::parse_duration_nanos;
 use crate::template::ANSI_BOLD;
 use crate::template::{aoc_cli, Day, ANSI_ITALIC, ANSI_RESET};
 
-pub fn run_part<I: Clone, T: Display>(func: impl Fn(I) -> Option<T>, input: I, day: Day, part: u8) {
+/// Arguments accepted by solution binaries. These are passed down by `solve`, `all` and `time`.
+pub const RUN_ARGS: [Arg; 8] = [
+    Arg::flag("--time", "Bench each part after running it."),
+    Arg::option(
+        "--submit",
+        "PART",
+        "Submit the result of a part via aoc-cli.",
+    ),
+    Arg::option(
+        "--profile-part",
+        "PART",
+        "Run a part in a loop without printing, for external profilers.",
+    ),
+    Arg::option("--iterations", "N", "Number of iterations when profiling."),
+    Arg::option(
+        "--duration",
+        "DURATION",
+        "Time to spend when profiling, e.g. `10s`. Defaults to 10s.",
+    ),
+    Arg::option(
+        "--dot",
+        "PATH",
+        "Write the graph registered by the day as Graphviz DOT.",
+    ),
+    Arg::option(
+        "--frames",
+        "DIR",
+        "Write the frames registered by the day to a directory.",
+    ),
+    Arg::option(
+        "--frame-format",
+        "FORMAT",
+        "Image format of frames: `pbm`, `png` or `ascii`. Defaults to `pbm`.",
+    ),
+];
+
+pub static RUN_COMMAND: Command = Command {
+    name: "run --bin <DAY> --",
+    about: "Run both parts of a solution.",
+    args: &RUN_ARGS,
+};
+
+static DEFAULT_PROFILE_DURATION: Duration = Duration::from_secs(10);
+
+/// Number of inputs cloned ahead of each timed batch, bounding memory for large inputs.
+const BENCH_BATCH_SIZE: u128 = 100;
+
+#[cfg(all(feature = "perf-counters", target_os = "linux"))]
+static PERF_UNAVAILABLE: std::sync::Once = std::sync::Once::new();
+
+/// Parsed options of a solution binary.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct RunOptions {
+    pub time: bool,
+    pub submit: Option<u8>,
+    pub profile_part: Option<u8>,
+    pub iterations: Option<u64>,
+    pub duration: Option<Duration>,
+    pub dot: Option<String>,
+    pub frames: Option<String>,
+    pub frame_format: Option<FrameFormat>,
+}
+
+impl RunOptions {
+    pub fn from_args<S: AsRef<str>>(args: &[S]) -> Result<Self, cli::Error> {
+        Self::from_matches(&RUN_COMMAND.parse(args)?)
+    }
+
+    /// Reads the options from the matches of a command accepting [`RUN_ARGS`], e.g. `solve`.
+    pub fn from_matches(matches: &Matches<'_>) -> Result<Self, cli::Error> {
+        let parse_part = |name: &'static str| -> Result<Option<u8>, cli::Error> {
+            let part = matches.parse::<u8>(name)?;
+            match part {
+                Some(part) if part != 1 && part != 2 => Err(cli::Error::InvalidValue {
+                    name,
+                    value: part.to_string(),
+                    reason: "expecting part 1 or 2".into(),
+                }),
+                _ => Ok(part),
+            }
+        };
+
+        let duration = match matches.value("--duration") {
+            Some(value) => Some(
+                parse_duration(value).ok_or_else(|| cli::Error::InvalidValue {
+                    name: "--duration",
+                    value: value.into(),
+                    reason: "expecting a duration like `10s` or `500ms`".into(),
+                })?,
+            ),
+            None => None,
+        };
+
+        let options = Self {
+            time: matches.flag("--time"),
+            submit: parse_part("--submit")?,
+            profile_part: parse_part("--profile-part")?,
+            iterations: matches.parse("--iterations")?,
+            duration,
+            dot: matches.value("--dot").map(String::from),
+            frames: matches.value("--frames").map(String::from),
+            frame_format: matches.parse("--frame-format")?,
+        };
+
+        if let (Some(_), Some(duration)) = (options.iterations, options.duration) {
+            return Err(cli::Error::InvalidValue {
+                name: "--duration",
+                value: format!("{duration:?}"),
+                reason: "cannot be combined with `--iterations`".into(),
+            });
+        }
+
+        Ok(options)
+    }
+
+    /// Parses the options of the current process, exiting with a message if they are invalid.
+    pub fn from_env() -> Self {
+        let args: Vec<String> = env::args().skip(1).collect();
+        match Self::from_args(&args) {
+            Ok(options) => options,
+            Err(cli::Error::Help(help)) => {
+                println!("{help}");
+                process::exit(0);
+            }
+            Err(e) => {
+                eprintln!("Error: {e}");
+                process::exit(1);
+            }
+        }
+    }
+
+    /// Converts the options back to arguments for invoking a solution binary.
+    pub fn to_args(&self) -> Vec<String> {
+        let mut args = vec![];
+        if self.time {
+            args.push("--time".into());
+        }
+        if let Some(part) = self.submit {
+            args.push("--submit".into());
+            args.push(part.to_string());
+        }
+        if let Some(part) = self.profile_part {
+            args.push("--profile-part".into());
+            args.push(part.to_string());
+        }
+        if let Some(iterations) = self.iterations {
+            args.push("--iterations".into());
+            args.push(iterations.to_string());
+        }
+        if let Some(duration) = self.duration {
+            args.push("--duration".into());
+            args.push(format!("{duration:?}"));
+        }
+        if let Some(path) = &self.dot {
+            args.push("--dot".into());
+            args.push(path.clone());
+        }
+        if let Some(dir) = &self.frames {
+            args.push("--frames".into());
+            args.push(dir.clone());
+        }
+        if let Some(format) = self.frame_format {
+            args.push("--frame-format".into());
+            args.push(format.extension().into());
+        }
+        args
+    }
+}
+
+pub fn run_part<I: Clone, T: Display>(
+    func: impl Fn(I) -> Option<T>,
+    input: I,
+    day: Day,
+    part: u8,
+    options: &RunOptions,
+) {
+    if let Some(profile_part) = options.profile_part {
+        if profile_part == part {
+            profile(func, input, part, options);
+        }
+        return;
+    }
+
     let part_str = format!("Part {part}");
 
-    let (result, duration, samples) =
-        run_timed(func, input, |result| print_result(result, &part_str, ""));
+    let (result, run) = run_timed(func, input, options.time, |result| {
+        print_result(result, &part_str, "")
+    });
 
-    print_result(&result, &part_str, &format_duration(&duration, samples));
+    print_result(&result, &part_str, &format_duration(&run));
+
+    if let Some(counters) = run.counters {
+        println!("{part_str} counters (main thread): {counters}");
+    }
 
     if let Some(result) = result {
-        submit_result(result, day, part);
+        if options.submit == Some(part) {
+            submit_result(result, day, part);
+        }
+    }
+}
+
+/// Visual exports a day registers with e.g. `solution!(DAY, dot = to_dot)`.
+#[derive(Default)]
+pub struct Exports {
+    pub dot: Option<fn(&str) -> Option<Graph>>,
+    pub frames: Option<fn(&str) -> Vec<Frame>>,
+}
+
+/// Writes the exports requested via `--dot` and `--frames`.
+pub fn run_exports(exports: &Exports, input: &str, day: Day, options: &RunOptions) {
+    if let Some(path) = &options.dot {
+        let Some(export) = exports.dot else {
+            eprintln!("Day {day} does not register a DOT export.");
+            process::exit(1);
+        };
+
+        let Some(graph) = export(input) else {
+            eprintln!("Failed to build the graph of day {day}.");
+            process::exit(1);
+        };
+
+        write_export(path, graph.to_string().as_bytes());
+        println!("{ANSI_ITALIC}Wrote graph to \"{path}\".{ANSI_RESET}");
+    }
+
+    if let Some(dir) = &options.frames {
+        let Some(export) = exports.frames else {
+            eprintln!("Day {day} does not register a frame export.");
+            process::exit(1);
+        };
+
+        let format = options.frame_format.unwrap_or(FrameFormat::Pbm);
+        let frames = export(input);
+
+        if let Err(e) = fs::create_dir_all(dir) {
+            eprintln!("Failed to create \"{dir}\": {e}");
+            process::exit(1);
+        }
+
+        for frame in &frames {
+            let path = Path::new(dir).join(format!("{}.{}", frame.name, format.extension()));
+            write_export(&path.to_string_lossy(), &frame.encode(format));
+        }
+
+        println!(
+            "{ANSI_ITALIC}Wrote {} frames to \"{dir}\".{ANSI_RESET}",
+            frames.len()
+        );
     }
 }
 
+fn write_export(path: &str, content: &[u8]) {
+    if let Err(e) = fs::write(path, content) {
+        eprintln!("Failed to write \"{path}\": {e}");
+        process::exit(1);
+    }
+}
+
+/// Measurements of running a solution part.
+struct Run {
+    duration: Duration,
+    stddev: Duration,
+    samples: u128,
+    /// Heap usage of the first run, only collected with the `dhat-heap` or `count-allocs` feature.
+    allocations: Option<AllocStats>,
+    /// Hardware counters per bench iteration, only collected with the `perf-counters` feature.
+    counters: Option<PerfCounters>,
+}
+
 /// Run a solution part. The behavior differs depending on whether we are running a release or debug build:
 ///  1. in debug, the function is executed once.
 ///  2. in release, the function is benched (approx. 1 second of execution time or 10 samples, whatever take longer.)
 fn run_timed<I: Clone, T>(
     func: impl Fn(I) -> T,
     input: I,
+    is_timed: bool,
     hook: impl Fn(&T),
-) -> (T, Duration, u128) {
+) -> (T, Run) {
     let timer = Instant::now();
-    let result = {
+    let (result, allocations) = {
         let input = input.clone();
 
+        // testing mode skips writing `dhat-heap.json`, the runner prints a summary instead.
+        #[cfg(feature = "dhat-heap")]
+        let _profiler = dhat::Profiler::builder().testing().build();
+
+        #[cfg(feature = "count-allocs")]
+        CountingAlloc::reset();
+
+        let result = func(input);
+
         #[cfg(feature = "dhat-heap")]
-        let _profiler = dhat::Profiler::new_heap();
+        let allocations = Some(AllocStats::from(dhat::HeapStats::get()));
+        #[cfg(feature = "count-allocs")]
+        let allocations = Some(CountingAlloc::stats());
+        #[cfg(not(any(feature = "dhat-heap", feature = "count-allocs")))]
+        let allocations = None;
 
-        func(input)
+        (result, allocations)
     };
     let base_time = timer.elapsed();
 
     hook(&result);
 
-    let run = if std::env::args().any(|x| x == "--time") {
+    let run = if is_timed {
         bench(func, input, &base_time)
     } else {
-        (base_time, 1)
+        Run {
+            duration: base_time,
+            stddev: Duration::ZERO,
+            samples: 1,
+            allocations: None,
+            counters: None,
+        }
     };
 
-    (result, run.0, run.1)
+    (result, Run { allocations, ..run })
 }
 
-fn bench<I: Clone, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) -> (Duration, u128) {
+fn bench<I: Clone, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) -> Run {
     let mut stdout = stdout();
 
     print!(" > {ANSI_ITALIC}benching{ANSI_RESET}");
@@ -61,21 +350,117 @@ fn bench<I: Clone, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) ->
     let bench_iterations =
         (Duration::from_secs(1).as_nanos() / cmp::max(base_time.as_nanos(), 10)).clamp(10, 10000);
 
-    let mut timers: Vec<Duration> = vec![];
+    #[allow(clippy::cast_possible_truncation)]
+    let mut timers: Vec<Duration> = Vec::with_capacity(bench_iterations as usize);
+    let overhead = timer_overhead();
 
-    for _ in 0..bench_iterations {
-        // need a clone here to make the borrow checker happy.
-        let cloned = input.clone();
-        let timer = Instant::now();
-        black_box(func(black_box(cloned)));
-        timers.push(timer.elapsed());
+    #[cfg(all(feature = "perf-counters", target_os = "linux"))]
+    let recorder = perf::Recorder::new();
+    #[cfg(all(feature = "perf-counters", target_os = "linux"))]
+    match &recorder {
+        Some(recorder) => recorder.reset(),
+        None => PERF_UNAVAILABLE.call_once(|| {
+            eprintln!(
+                "Hardware counters are unavailable, check `/proc/sys/kernel/perf_event_paranoid`."
+            );
+        }),
     }
 
-    (
-        #[allow(clippy::cast_possible_truncation)]
-        Duration::from_nanos(average_duration(&timers) as u64),
-        bench_iterations,
-    )
+    let mut remaining = bench_iterations;
+
+    while remaining > 0 {
+        let batch_size = remaining.min(BENCH_BATCH_SIZE);
+        remaining -= batch_size;
+
+        // clone ahead of the timed region, so that only the solve is measured.
+        let batch: Vec<I> = (0..batch_size).map(|_| input.clone()).collect();
+        let mut outputs = Vec::with_capacity(batch.len());
+
+        #[cfg(all(feature = "perf-counters", target_os = "linux"))]
+        if let Some(recorder) = &recorder {
+            recorder.resume();
+        }
+
+        for cloned in batch {
+            let timer = Instant::now();
+            let output = black_box(func(black_box(cloned)));
+            timers.push(timer.elapsed().saturating_sub(overhead));
+            outputs.push(output);
+        }
+
+        #[cfg(all(feature = "perf-counters", target_os = "linux"))]
+        if let Some(recorder) = &recorder {
+            recorder.pause();
+        }
+
+        // outputs are dropped outside of the timed region as well.
+        drop(outputs);
+    }
+
+    #[cfg(all(feature = "perf-counters", target_os = "linux"))]
+    let counters = recorder.map(|r| r.read(bench_iterations as u64));
+    #[cfg(not(all(feature = "perf-counters", target_os = "linux")))]
+    let counters = None;
+
+    let average = average_duration(&timers);
+
+    #[allow(clippy::cast_possible_truncation)]
+    Run {
+        duration: Duration::from_nanos(average as u64),
+        stddev: Duration::from_nanos(stddev_duration(&timers, average) as u64),
+        samples: bench_iterations,
+        allocations: None,
+        counters,
+    }
+}
+
+/// Median cost of reading the timer, subtracted from each bench sample.
+fn timer_overhead() -> Duration {
+    static OVERHEAD: OnceLock<Duration> = OnceLock::new();
+
+    *OVERHEAD.get_or_init(|| {
+        let mut samples: Vec<Duration> = (0..1000)
+            .map(|_| {
+                let timer = Instant::now();
+                black_box(timer).elapsed()
+            })
+            .collect();
+
+        samples.sort_unstable();
+        samples[samples.len() / 2]
+    })
+}
+
+/// Calls a part repeatedly without printing, so external profilers get a clean sample.
+fn profile<I: Clone, T>(func: impl Fn(I) -> T, input: I, part: u8, options: &RunOptions) {
+    let duration = options.duration.unwrap_or(DEFAULT_PROFILE_DURATION);
+    let timer = Instant::now();
+    let mut iterations: u64 = 0;
+
+    loop {
+        black_box(func(black_box(input.clone())));
+        iterations += 1;
+
+        let is_done = match options.iterations {
+            Some(limit) => iterations >= limit,
+            None => timer.elapsed() >= duration,
+        };
+
+        if is_done {
+            break;
+        }
+    }
+
+    eprintln!(
+        "Profiled part {part}: {iterations} iterations in {:.1?}",
+        timer.elapsed()
+    );
+}
+
+/// Parses a duration like `10s`, `1.5s` or `500ms`.
+pub fn parse_duration(s: &str) -> Option<Duration> {
+    let nanos = parse_duration_nanos(s)?;
+    (nanos.is_finite() && nanos > 0.0).then(|| Duration::from_secs_f64(nanos / 1e9))
 }
 
 fn average_duration(numbers: &[Duration]) -> u128 {
@@ -86,11 +471,38 @@ fn average_duration(numbers: &[Duration]) -> u128 {
         / numbers.len() as u128
 }
 
-fn format_duration(duration: &Duration, samples: u128) -> String {
-    if samples == 1 {
-        format!(" ({duration:.1?})")
+/// Sample standard deviation in nanoseconds.
+#[allow(clippy::cast_precision_loss)]
+fn stddev_duration(numbers: &[Duration], average: u128) -> f64 {
+    if numbers.len() < 2 {
+        return 0.0;
+    }
+
+    let average = average as f64;
+    let variance = numbers
+        .iter()
+        .map(|d| (d.as_nanos() as f64 - average).powi(2))
+        .sum::<f64>()
+        / (numbers.len() - 1) as f64;
+
+    variance.sqrt()
+}
+
+fn format_duration(run: &Run) -> String {
+    let Run {
+        duration,
+        stddev,
+        samples,
+        allocations,
+        ..
+    } = run;
+
+    let allocations = allocations.map_or_else(String::new, |a| format!(", {a}"));
+
+    if *samples == 1 {
+        format!(" ({duration:.1?}{allocations})")
     } else {
-        format!(" ({duration:.1?} @ {samples} samples)")
+        format!(" ({duration:.1?} ± {stddev:.1?} @ {samples} samples{allocations})")
     }
 }
 
@@ -129,41 +541,93 @@ fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str)
     }
 }
 
-/// Parse the arguments passed to `solve` and try to submit one part of the solution if:
-///  1. we are in `--release` mode.
-///  2. aoc-cli is installed.
+/// Try to submit one part of the solution if aoc-cli is installed.
 fn submit_result<T: Display>(
     result: T,
     day: Day,
     part: u8,
 ) -> Option<Result<Output, aoc_cli::AocCommandError>> {
-    let args: Vec<String> = env::args().collect();
-
-    if !args.contains(&"--submit".into()) {
-        return None;
-    }
-
-    if args.len() < 3 {
-        eprintln!("Unexpected command-line input. Format: cargo solve 1 --submit 1");
+    if aoc_cli::check().is_err() {
+        eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
         process::exit(1);
     }
 
-    let part_index = args.iter().position(|x| x == "--submit").unwrap() + 1;
+    println!("Submitting result via aoc-cli...");
+    Some(aoc_cli::submit(day, part, &result.to_string()))
+}
 
-    let Ok(part_submit) = args[part_index].parse::<u8>() else {
-        eprintln!("Unexpected command-line input. Format: cargo solve 1 --submit 1");
-        process::exit(1);
-    };
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::thread;
+    use std::time::Duration;
+
+    use super::{bench, RunOptions, RUN_ARGS};
+    use crate::frame::FrameFormat;
+    use crate::template::cli::{self, Arg, Command};
 
-    if part_submit != part {
-        return None;
+    /// An input that is slow to clone, but free to solve.
+    struct SlowClone;
+
+    impl Clone for SlowClone {
+        fn clone(&self) -> Self {
+            thread::sleep(Duration::from_millis(1));
+            SlowClone
+        }
     }
 
-    if aoc_cli::check().is_err() {
-        eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
-        process::exit(1);
+    #[test]
+    fn excludes_clone_cost_from_bench() {
+        let run = bench(|_: SlowClone| 1, SlowClone, &Duration::from_millis(100));
+        assert_eq!(run.samples, 10);
+        assert!(run.duration < Duration::from_micros(500));
     }
 
-    println!("Submitting result via aoc-cli...");
-    Some(aoc_cli::submit(day, part, &result.to_string()))
+    #[test]
+    fn roundtrips_run_options() {
+        let options = RunOptions {
+            submit: Some(2),
+            profile_part: Some(1),
+            duration: Some(Duration::from_millis(1500)),
+            dot: Some("out.dot".into()),
+            frame_format: Some(FrameFormat::Ascii),
+            ..Default::default()
+        };
+        assert_eq!(RunOptions::from_args(&options.to_args()).unwrap(), options);
+    }
+
+    #[test]
+    fn rejects_invalid_profile_options() {
+        assert!(RunOptions::from_args(&["--profile-part", "3"]).is_err());
+        assert!(RunOptions::from_args(&["--duration", "soon"]).is_err());
+        assert!(RunOptions::from_args(&[
+            "--profile-part",
+            "1",
+            "--iterations",
+            "5",
+            "--duration",
+            "1s"
+        ])
+        .is_err());
+    }
+
+    #[test]
+    fn reads_options_of_wrapping_commands() {
+        static ARGS: [Arg; 1 + RUN_ARGS.len()] =
+            cli::concat(&[Arg::positional("DAY", "The day to run.")], &RUN_ARGS);
+        let solve = Command {
+            name: "solve",
+            about: "Run a solution.",
+            args: &ARGS,
+        };
+
+        let matches = solve
+            .parse(&["3", "--submit", "2", "--dot", "out.dot"])
+            .unwrap();
+        let options = RunOptions::from_matches(&matches).unwrap();
+        assert_eq!(options.submit, Some(2));
+        assert_eq!(options.dot.as_deref(), Some("out.dot"));
+
+        let matches = solve.parse(&["3", "--submit", "3"]).unwrap();
+        assert!(RunOptions::from_matches(&matches).is_err());
+    }
 }
@@ -0,0 +1,207 @@
+/// Module that compares benchmark timings across rayon pool sizes.
+use std::str::FromStr;
+
+use crate::template::compare::format_nanos;
+use crate::template::timings::{Timing, Timings};
+use crate::template::{Day, ANSI_BOLD, ANSI_RESET};
+
+/// Comma-separated list of thread counts, e.g. `1,2,4,8`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ThreadCounts(pub Vec<usize>);
+
+impl FromStr for ThreadCounts {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut counts: Vec<usize> = vec![];
+
+        for part in s.split(',').map(str::trim) {
+            let count: usize = part
+                .parse()
+                .map_err(|_| format!("\"{part}\" is not a thread count"))?;
+
+            if count == 0 {
+                return Err("thread counts start at 1".into());
+            }
+
+            if !counts.contains(&count) {
+                counts.push(count);
+            }
+        }
+
+        Ok(ThreadCounts(counts))
+    }
+}
+
+/// Total time of a day for each thread count it was run with.
+#[derive(Debug, PartialEq)]
+pub struct DayScaling {
+    pub day: Day,
+    pub runs: Vec<(usize, f64)>,
+}
+
+impl DayScaling {
+    /// Speedup of a run compared to the first thread count.
+    pub fn speedup(&self, index: usize) -> f64 {
+        self.runs[0].1 / self.runs[index].1
+    }
+}
+
+/// Collects the total time per day and thread count, in the order of `runs`.
+pub fn scaling(runs: &[(usize, Timings)]) -> Vec<DayScaling> {
+    let Some((_, first)) = runs.first() else {
+        return vec![];
+    };
+
+    first
+        .data
+        .iter()
+        .map(|timing| DayScaling {
+            day: timing.day,
+            runs: runs
+                .iter()
+                .filter_map(|(threads, timings)| {
+                    Some((*threads, timings.get(timing.day)?.total_nanos))
+                })
+                .collect(),
+        })
+        .collect()
+}
+
+/// Minimum speedup over the fewest threads for a day to count as parallel.
+const MIN_SPEEDUP: f64 = 1.2;
+
+/// Picks the run to store for each day. Days that scale keep their fastest run, which records
+/// the thread count it was taken with. Any other day keeps its run with the fewest threads and
+/// records none, as the differences between its runs are noise.
+pub fn fastest(mut runs: Vec<(usize, Timings)>) -> Timings {
+    runs.sort_by_key(|(threads, _)| *threads);
+
+    // the fewest-threads and the fastest run of each day.
+    let mut picks: Vec<(Timing, Timing)> = vec![];
+
+    for (_, timings) in runs {
+        for timing in timings.data {
+            match picks.iter_mut().find(|(first, _)| first.day == timing.day) {
+                Some((_, fastest)) if fastest.total_nanos <= timing.total_nanos => {}
+                Some((_, fastest)) => *fastest = timing,
+                None => picks.push((timing.clone(), timing)),
+            }
+        }
+    }
+
+    let mut data: Vec<Timing> = picks
+        .into_iter()
+        .map(|(first, fastest)| {
+            if first.total_nanos / fastest.total_nanos >= MIN_SPEEDUP {
+                fastest
+            } else {
+                Timing {
+                    threads: None,
+                    ..first
+                }
+            }
+        })
+        .collect();
+
+    data.sort_unstable_by_key(|t| t.day);
+
+    Timings {
+        data,
+        ..Default::default()
+    }
+}
+
+pub fn print(rows: &[DayScaling]) {
+    println!();
+    println!("{ANSI_BOLD}Scaling{ANSI_RESET}");
+    println!("------");
+
+    for row in rows {
+        let runs: Vec<String> = row
+            .runs
+            .iter()
+            .enumerate()
+            .map(|(index, (threads, nanos))| {
+                let unit = if *threads == 1 { "thread" } else { "threads" };
+                let time = format_nanos(*nanos);
+                if index == 0 {
+                    format!("{threads} {unit} {time}")
+                } else {
+                    format!("{threads} {unit} {time} ({:.2}x)", row.speedup(index))
+                }
+            })
+            .collect();
+
+        println!("Day {}: {}", row.day, runs.join(", "));
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{fastest, scaling, ThreadCounts};
+    use crate::template::timings::{Timing, Timings};
+    use crate::template::Day;
+
+    fn timings(threads: usize, total_nanos: &[f64]) -> (usize, Timings) {
+        let data = total_nanos
+            .iter()
+            .enumerate()
+            .map(|(i, total_nanos)| Timing {
+                day: Day::new(6 + u8::try_from(i).unwrap()).unwrap(),
+                part_1: Some("1ms".into()),
+                part_2: None,
+                total_nanos: *total_nanos,
+                threads: Some(threads),
+                metrics: Default::default(),
+            })
+            .collect();
+
+        (
+            threads,
+            Timings {
+                data,
+                ..Default::default()
+            },
+        )
+    }
+
+    #[test]
+    fn parses_thread_counts() {
+        assert_eq!(
+            "1, 2,4,2".parse::<ThreadCounts>(),
+            Ok(ThreadCounts(vec![1, 2, 4]))
+        );
+        assert!("0,2".parse::<ThreadCounts>().is_err());
+        assert!("one".parse::<ThreadCounts>().is_err());
+    }
+
+    #[test]
+    fn computes_speedup() {
+        let rows = scaling(&[timings(1, &[8e6, 1e6]), timings(4, &[2e6, 1e6])]);
+        assert_eq!(rows.len(), 2);
+        assert_eq!(rows[0].runs, vec![(1, 8e6), (4, 2e6)]);
+        assert_eq!(rows[0].speedup(1), 4.0);
+        assert_eq!(rows[1].speedup(1), 1.0);
+    }
+
+    #[test]
+    fn keeps_fastest_run() {
+        let timings = fastest(vec![timings(4, &[2e6, 3e6]), timings(1, &[8e6, 1e6])]);
+        assert_eq!(timings.data[0].threads, Some(4));
+        assert_eq!(timings.data[0].total_nanos, 2e6);
+        assert_eq!(timings.data[1].threads, None);
+        assert_eq!(timings.data[1].total_nanos, 1e6);
+    }
+
+    #[test]
+    fn ignores_thread_counts_of_flat_days() {
+        let timings = fastest(vec![
+            timings(1, &[5.0e6]),
+            timings(4, &[4.9e6]),
+            timings(8, &[4.8e6]),
+        ]);
+        assert_eq!(timings.data[0].threads, None);
+        assert_eq!(timings.data[0].total_nanos, 5.0e6);
+    }
+}
@@ -1,9 +1,14 @@
 use std::{collections::HashMap, fs, io::Error, str::FromStr};
 use tinyjson::JsonValue;
 
+use crate::template::alloc::AllocStats;
+use crate::template::perf::PerfCounters;
 use crate::template::Day;
 
-static TIMINGS_FILE_PATH: &str = "./data/timings.json";
+pub static TIMINGS_FILE_PATH: &str = "./data/timings.json";
+
+/// Days with a total run time above this are considered slow, unless a different threshold is stored.
+static DEFAULT_SLOW_THRESHOLD_MILLIS: f64 = 100.0;
 
 /// Represents benchmark times for a single day.
 #[derive(Clone, Debug)]
@@ -12,6 +17,63 @@ pub struct Timing {
     pub part_1: Option<String>,
     pub part_2: Option<String>,
     pub total_nanos: f64,
+    /// Size of the rayon pool the timing was taken with. Only present for runs with `--threads`.
+    pub threads: Option<usize>,
+    /// Additional measurements for part 1 and part 2. Only present for newer timings.
+    pub metrics: [PartMetrics; 2],
+}
+
+/// Distribution of the bench samples, heap usage and hardware counters of a part.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct PartMetrics {
+    pub stddev_nanos: Option<f64>,
+    pub samples: Option<u64>,
+    pub allocations: Option<AllocStats>,
+    /// Counters of the main thread only, work on rayon threads is not included.
+    pub counters: Option<PerfCounters>,
+}
+
+impl PartMetrics {
+    fn is_empty(&self) -> bool {
+        *self == PartMetrics::default()
+    }
+}
+
+impl Timing {
+    pub fn part(&self, part: u8) -> Option<&str> {
+        match part {
+            1 => self.part_1.as_deref(),
+            2 => self.part_2.as_deref(),
+            _ => None,
+        }
+    }
+
+    pub fn metrics(&self, part: u8) -> Option<&PartMetrics> {
+        self.metrics.get(usize::from(part).checked_sub(1)?)
+    }
+
+    /// Duration of a part in nanoseconds, parsed from its formatted string.
+    pub fn part_nanos(&self, part: u8) -> Option<f64> {
+        match part {
+            1 => self.part_1.as_deref().and_then(parse_duration_nanos),
+            2 => self.part_2.as_deref().and_then(parse_duration_nanos),
+            _ => None,
+        }
+    }
+}
+
+/// Parses a duration formatted by `Duration`'s `Debug` impl, e.g. `74.13µs`, into nanoseconds.
+pub fn parse_duration_nanos(s: &str) -> Option<f64> {
+    // for possible time formats, see: https://github.com/rust-lang/rust/blob/1.64.0/library/core/src/time.rs#L1176-L1200
+    let parse_to_float =
+        |s: &str, postfix: &str| -> Option<f64> { s.split(postfix).next()?.parse().ok() };
+
+    match s.trim() {
+        s if s.contains("ns") => parse_to_float(s, "ns"),
+        s if s.contains("µs") => parse_to_float(s, "µs").map(|x| x * 1000_f64),
+        s if s.contains("ms") => parse_to_float(s, "ms").map(|x| x * 1_000_000_f64),
+        s => parse_to_float(s, "s").map(|x| x * 1_000_000_000_f64),
+    }
 }
 
 /// Represents benchmark times for a set of days.
@@ -19,6 +81,7 @@ pub struct Timing {
 #[derive(Clone, Debug, Default)]
 pub struct Timings {
     pub data: Vec<Timing>,
+    pub slow_threshold_millis: Option<f64>,
 }
 
 impl Timings {
@@ -58,8 +121,31 @@ impl Timings {
             }
         }
 
-        data.sort_unstable_by(|a, b| a.day.cmp(&b.day));
-        Timings { data }
+        data.sort_unstable_by_key(|a| a.day);
+        Timings {
+            data,
+            slow_threshold_millis: new.slow_threshold_millis.or(self.slow_threshold_millis),
+        }
+    }
+
+    /// Copy allocation stats from `new` to the matching days, keeping all other measurements.
+    /// Used for profiling runs, where durations are skewed by the profiler.
+    pub fn merge_allocations(&self, new: &Self) -> Self {
+        let mut merged = self.clone();
+
+        for timing in &mut merged.data {
+            let Some(other) = new.get(timing.day) else {
+                continue;
+            };
+
+            for (metrics, other) in timing.metrics.iter_mut().zip(&other.metrics) {
+                if other.allocations.is_some() {
+                    metrics.allocations = other.allocations;
+                }
+            }
+        }
+
+        merged
     }
 
     /// Sum up total duration of timings as millis.
@@ -72,6 +158,21 @@ impl Timings {
             .iter()
             .any(|t| t.day == day && t.part_1.is_some() && t.part_2.is_some())
     }
+
+    /// Whether the total run time of a day is above the (stored or default) slow threshold.
+    pub fn is_day_slow(&self, day: Day) -> bool {
+        let threshold = self
+            .slow_threshold_millis
+            .unwrap_or(DEFAULT_SLOW_THRESHOLD_MILLIS);
+
+        self.data
+            .iter()
+            .any(|t| t.day == day && t.total_nanos / 1_000_000_f64 > threshold)
+    }
+
+    pub fn get(&self, day: Day) -> Option<&Timing> {
+        self.data.iter().find(|t| t.day == day)
+    }
 }
 
 /* -------------------------------------------------------------------------- */
@@ -85,6 +186,10 @@ impl From<Timings> for JsonValue {
             JsonValue::Array(value.data.iter().map(JsonValue::from).collect()),
         );
 
+        if let Some(threshold) = value.slow_threshold_millis {
+            map.insert("slow_threshold_millis".into(), JsonValue::Number(threshold));
+        }
+
         JsonValue::Object(map)
     }
 }
@@ -95,19 +200,30 @@ impl TryFrom<String> for Timings {
     fn try_from(value: String) -> Result<Self, Self::Error> {
         let json = JsonValue::from_str(&value).or(Err("not valid JSON file."))?;
 
-        let json_data = json
+        let json_object = json
             .get::<HashMap<String, JsonValue>>()
-            .ok_or("expected JSON document to be an object.")?
+            .ok_or("expected JSON document to be an object.")?;
+
+        let json_data = json_object
             .get("data")
             .ok_or("expected JSON document to have key `data`.")?
             .get::<Vec<JsonValue>>()
             .ok_or("expected `json.data` to be an array.")?;
 
+        let slow_threshold_millis = match json_object.get("slow_threshold_millis") {
+            Some(v) => Some(
+                *v.get::<f64>()
+                    .ok_or("expected `json.slow_threshold_millis` to be a number.")?,
+            ),
+            None => None,
+        };
+
         Ok(Timings {
             data: json_data
                 .iter()
                 .map(Timing::try_from)
                 .collect::<Result<_, _>>()?,
+            slow_threshold_millis,
         })
     }
 }
@@ -121,6 +237,11 @@ impl From<&Timing> for JsonValue {
         map.insert("day".into(), JsonValue::String(value.day.to_string()));
         map.insert("total_nanos".into(), JsonValue::Number(value.total_nanos));
 
+        if let Some(threads) = value.threads {
+            #[allow(clippy::cast_precision_loss)]
+            map.insert("threads".into(), JsonValue::Number(threads as f64));
+        }
+
         let part_1 = value.part_1.clone().map(JsonValue::String);
         let part_2 = value.part_2.clone().map(JsonValue::String);
 
@@ -140,6 +261,15 @@ impl From<&Timing> for JsonValue {
             },
         );
 
+        for (key, metrics) in ["part_1_metrics", "part_2_metrics"]
+            .into_iter()
+            .zip(&value.metrics)
+        {
+            if !metrics.is_empty() {
+                map.insert(key.into(), JsonValue::from(metrics));
+            }
+        }
+
         JsonValue::Object(map)
     }
 }
@@ -173,11 +303,159 @@ impl TryFrom<&JsonValue> for Timing {
             .and_then(|v| v.get::<f64>().copied())
             .ok_or("Expected timing.total_nanos to be a number.")?;
 
+        let threads = match json.get("threads") {
+            #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+            Some(v) => Some(
+                v.get::<f64>()
+                    .map(|x| *x as usize)
+                    .ok_or("Expected timing.threads to be a number.")?,
+            ),
+            None => None,
+        };
+
+        let mut metrics = [PartMetrics::default(); 2];
+        for (key, metrics) in ["part_1_metrics", "part_2_metrics"]
+            .into_iter()
+            .zip(&mut metrics)
+        {
+            if let Some(value) = json.get(key) {
+                *metrics = PartMetrics::try_from(value)?;
+            }
+        }
+
         Ok(Timing {
             day,
             part_1: part_1.cloned(),
             part_2: part_2.cloned(),
             total_nanos,
+            threads,
+            metrics,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&PartMetrics> for JsonValue {
+    fn from(value: &PartMetrics) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        if let Some(stddev_nanos) = value.stddev_nanos {
+            map.insert("stddev_nanos".into(), JsonValue::Number(stddev_nanos));
+        }
+
+        if let Some(samples) = value.samples {
+            #[allow(clippy::cast_precision_loss)]
+            map.insert("samples".into(), JsonValue::Number(samples as f64));
+        }
+
+        if let Some(allocations) = value.allocations {
+            #[allow(clippy::cast_precision_loss)]
+            let fields = [
+                ("total_bytes", allocations.total_bytes),
+                ("total_blocks", allocations.total_blocks),
+                ("peak_bytes", allocations.peak_bytes),
+                ("peak_blocks", allocations.peak_blocks),
+            ]
+            .into_iter()
+            .map(|(key, value)| (key.to_string(), JsonValue::Number(value as f64)))
+            .collect();
+
+            map.insert("allocations".into(), JsonValue::Object(fields));
+        }
+
+        if let Some(counters) = value.counters {
+            #[allow(clippy::cast_precision_loss)]
+            let fields = [
+                ("instructions", counters.instructions),
+                ("cycles", counters.cycles),
+                ("cache_misses", counters.cache_misses),
+                ("branch_misses", counters.branch_misses),
+            ]
+            .into_iter()
+            .filter_map(|(key, value)| Some((key.to_string(), JsonValue::Number(value? as f64))))
+            .collect();
+
+            map.insert("counters".into(), JsonValue::Object(fields));
+        }
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for PartMetrics {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected timing metrics to be a JSON object.")?;
+
+        let number = |key: &str| -> Result<Option<f64>, String> {
+            match json.get(key) {
+                Some(v) => Ok(Some(
+                    *v.get::<f64>()
+                        .ok_or(format!("Expected metrics.{key} to be a number."))?,
+                )),
+                None => Ok(None),
+            }
+        };
+
+        let allocations = match json.get("allocations") {
+            Some(v) => {
+                let json = v
+                    .get::<HashMap<String, JsonValue>>()
+                    .ok_or("Expected metrics.allocations to be a JSON object.")?;
+
+                #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+                let field = |key: &str| -> Result<u64, String> {
+                    json.get(key)
+                        .and_then(|v| v.get::<f64>())
+                        .map(|x| *x as u64)
+                        .ok_or(format!(
+                            "Expected metrics.allocations.{key} to be a number."
+                        ))
+                };
+
+                Some(AllocStats {
+                    total_bytes: field("total_bytes")?,
+                    total_blocks: field("total_blocks")?,
+                    peak_bytes: field("peak_bytes")?,
+                    peak_blocks: field("peak_blocks")?,
+                })
+            }
+            None => None,
+        };
+
+        let counters = match json.get("counters") {
+            Some(v) => {
+                let json = v
+                    .get::<HashMap<String, JsonValue>>()
+                    .ok_or("Expected metrics.counters to be a JSON object.")?;
+
+                #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+                let field = |key: &str| {
+                    json.get(key)
+                        .and_then(|v| v.get::<f64>())
+                        .map(|x| *x as u64)
+                };
+
+                Some(PerfCounters {
+                    instructions: field("instructions"),
+                    cycles: field("cycles"),
+                    cache_misses: field("cache_misses"),
+                    branch_misses: field("branch_misses"),
+                })
+            }
+            None => None,
+        };
+
+        Ok(PartMetrics {
+            stddev_nanos: number("stddev_nanos")?,
+            #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+            samples: number("samples")?.map(|x| x as u64),
+            allocations,
+            counters,
         })
     }
 }
@@ -198,25 +476,33 @@ mod tests {
                     part_1: Some("10ms".into()),
                     part_2: Some("20ms".into()),
                     total_nanos: 3e+10,
+                    threads: None,
+                    metrics: Default::default(),
                 },
                 Timing {
                     day: day!(2),
                     part_1: Some("30ms".into()),
                     part_2: Some("40ms".into()),
                     total_nanos: 7e+10,
+                    threads: None,
+                    metrics: Default::default(),
                 },
                 Timing {
                     day: day!(4),
                     part_1: Some("40ms".into()),
                     part_2: None,
                     total_nanos: 4e+10,
+                    threads: None,
+                    metrics: Default::default(),
                 },
             ],
+            ..Default::default()
         }
     }
 
     mod deserialization {
         use crate::{day, template::timings::Timings};
+        use tinyjson::JsonValue;
 
         #[test]
         fn handles_json_timings() {
@@ -235,6 +521,50 @@ mod tests {
             let json = r#"{ "data": [] }"#.to_string();
             let timings = Timings::try_from(json).unwrap();
             assert_eq!(timings.data.len(), 0);
+            assert_eq!(timings.slow_threshold_millis, None);
+        }
+
+        #[test]
+        fn handles_slow_threshold() {
+            let json = r#"{ "data": [], "slow_threshold_millis": 50 }"#.to_string();
+            let timings = Timings::try_from(json).unwrap();
+            assert_eq!(timings.slow_threshold_millis, Some(50_f64));
+        }
+
+        #[test]
+        fn handles_part_metrics() {
+            let json = r#"{ "data": [{ "day": "01", "part_1": "1ms", "part_2": "2ms", "total_nanos": 3000000, "part_2_metrics": { "stddev_nanos": 1500, "samples": 100 } }] }"#.to_string();
+            let timings = Timings::try_from(json).unwrap();
+            let timing = timings.data.first().unwrap();
+            assert!(timing.metrics(1).unwrap().stddev_nanos.is_none());
+            assert_eq!(timing.metrics(2).unwrap().stddev_nanos, Some(1500_f64));
+            assert_eq!(timing.metrics(2).unwrap().samples, Some(100));
+        }
+
+        #[test]
+        fn handles_allocations() {
+            let json = r#"{ "data": [{ "day": "01", "part_1": "1ms", "part_2": null, "total_nanos": 1000000, "part_1_metrics": { "allocations": { "total_bytes": 2048, "total_blocks": 3, "peak_bytes": 1024, "peak_blocks": 1 } } }] }"#.to_string();
+            let timings = Timings::try_from(json).unwrap();
+            let allocations = timings.data[0].metrics(1).unwrap().allocations.unwrap();
+            assert_eq!(allocations.total_bytes, 2048);
+            assert_eq!(allocations.peak_blocks, 1);
+
+            let roundtrip = JsonValue::from(timings.clone()).stringify().unwrap();
+            let timings = Timings::try_from(roundtrip).unwrap();
+            assert_eq!(timings.data[0].metrics[0].allocations, Some(allocations));
+        }
+
+        #[test]
+        fn handles_counters() {
+            let json = r#"{ "data": [{ "day": "01", "part_1": "1ms", "part_2": null, "total_nanos": 1000000, "part_1_metrics": { "counters": { "instructions": 1200, "cycles": 800 } } }] }"#.to_string();
+            let timings = Timings::try_from(json).unwrap();
+            let counters = timings.data[0].metrics(1).unwrap().counters.unwrap();
+            assert_eq!(counters.instructions, Some(1200));
+            assert_eq!(counters.cache_misses, None);
+
+            let roundtrip = JsonValue::from(timings.clone()).stringify().unwrap();
+            let timings = Timings::try_from(roundtrip).unwrap();
+            assert_eq!(timings.data[0].metrics[0].counters, Some(counters));
         }
 
         #[test]
@@ -289,10 +619,13 @@ mod tests {
                     part_1: Some("1ms".into()),
                     part_2: Some("2ms".into()),
                     total_nanos: 3_000_000_000_f64,
+                    threads: None,
+                    metrics: Default::default(),
                 }],
+                ..Default::default()
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), true);
+            assert_eq!(timings.is_day_complete(day!(1)), true);
         }
 
         #[test]
@@ -303,10 +636,13 @@ mod tests {
                     part_1: Some("1ms".into()),
                     part_2: None,
                     total_nanos: 1_000_000_000_f64,
+                    threads: None,
+                    metrics: Default::default(),
                 }],
+                ..Default::default()
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), false);
+            assert_eq!(timings.is_day_complete(day!(1)), false);
         }
 
         #[test]
@@ -317,17 +653,57 @@ mod tests {
                     part_1: None,
                     part_2: None,
                     total_nanos: 0.0,
+                    threads: None,
+                    metrics: Default::default(),
                 }],
+                ..Default::default()
+            };
+
+            assert_eq!(timings.is_day_complete(day!(1)), false);
+        }
+    }
+
+    mod is_day_slow {
+        use crate::{day, template::timings::Timings};
+
+        use super::get_mock_timings;
+
+        #[test]
+        fn handles_default_threshold() {
+            let timings = get_mock_timings();
+            assert_eq!(timings.is_day_slow(day!(1)), true);
+            assert_eq!(timings.is_day_slow(day!(3)), false);
+        }
+
+        #[test]
+        fn handles_stored_threshold() {
+            let timings = Timings {
+                slow_threshold_millis: Some(50_000_f64),
+                ..get_mock_timings()
             };
+            assert_eq!(timings.is_day_slow(day!(1)), false);
+            assert_eq!(timings.is_day_slow(day!(2)), true);
+        }
+    }
 
-            assert_eq!(timings.is_day_complete(&day!(1)), false);
+    mod parse_duration_nanos {
+        use crate::template::timings::parse_duration_nanos;
+
+        #[test]
+        fn parses_durations() {
+            assert_eq!(parse_duration_nanos("74.13ns"), Some(74.13));
+            assert_eq!(parse_duration_nanos("2µs"), Some(2000.0));
+            assert_eq!(parse_duration_nanos("1.5ms"), Some(1_500_000.0));
+            assert_eq!(parse_duration_nanos("2s"), Some(2_000_000_000.0));
+            assert_eq!(parse_duration_nanos("-"), None);
         }
     }
 
     mod merge {
         use crate::{
             day,
-            template::timings::{Timing, Timings},
+            template::alloc::AllocStats,
+            template::timings::{PartMetrics, Timing, Timings},
         };
 
         use super::get_mock_timings;
@@ -341,7 +717,10 @@ mod tests {
                     part_1: None,
                     part_2: None,
                     total_nanos: 0_f64,
+                    threads: None,
+                    metrics: Default::default(),
                 }],
+                ..Default::default()
             };
             let merged = timings.merge(&other);
             assert_eq!(merged.data.len(), 4);
@@ -361,7 +740,10 @@ mod tests {
                     part_1: None,
                     part_2: None,
                     total_nanos: 0_f64,
+                    threads: None,
+                    metrics: Default::default(),
                 }],
+                ..Default::default()
             };
             let merged = timings.merge(&other);
 
@@ -380,6 +762,38 @@ mod tests {
             assert_eq!(merged.data.len(), 3);
         }
 
+        #[test]
+        fn merges_allocations_only() {
+            let timings = get_mock_timings();
+            let allocations = AllocStats {
+                total_bytes: 10,
+                total_blocks: 1,
+                peak_bytes: 10,
+                peak_blocks: 1,
+            };
+            let other = Timings {
+                data: vec![Timing {
+                    day: day!(2),
+                    part_1: Some("1s".into()),
+                    part_2: None,
+                    total_nanos: 1e9,
+                    threads: None,
+                    metrics: [
+                        PartMetrics {
+                            allocations: Some(allocations),
+                            ..Default::default()
+                        },
+                        PartMetrics::default(),
+                    ],
+                }],
+                ..Default::default()
+            };
+            let merged = timings.merge_allocations(&other);
+            assert_eq!(merged.data[1].part_1, Some("30ms".into()));
+            assert_eq!(merged.data[1].metrics[0].allocations, Some(allocations));
+            assert_eq!(merged.data[1].metrics[1].allocations, None);
+        }
+
         #[test]
         fn handles_empty_other_timings() {
             let timings = get_mock_timings();